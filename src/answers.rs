@@ -0,0 +1,295 @@
+// src/answers.rs
+
+use anyhow::Result;
+
+use crate::model::{Answer, AnswerDetails, Project};
+use crate::timecode::{format_clock_ms, format_timecode_ms};
+use crate::timeline::{ClipCue, timeline};
+
+/// Clips of one round (or the top-level clips), in playing order.
+struct Section<'a> {
+    /// Round title and start of its title card (`None` for top-level clips)
+    heading: Option<(&'a str, u64)>,
+    cues: Vec<ClipCue<'a>>,
+}
+
+/// Title of the sheet: the intro title, if any.
+fn sheet_title(p: &Project) -> &str {
+    p.intro
+        .as_ref()
+        .map(|i| i.title.trim())
+        .filter(|t| !t.is_empty())
+        .unwrap_or("Blind test")
+}
+
+fn sections(p: &Project) -> Result<Vec<Section<'_>>> {
+    let t = timeline(p)?;
+    let mut sections: Vec<Section> = Vec::new();
+
+    for cue in t.clips {
+        let heading = cue.round.map(|r| {
+            let round = &t.rounds[r];
+            (round.round.title.as_str(), round.title.start_ms)
+        });
+        match sections.last_mut() {
+            Some(s) if s.heading == heading => s.cues.push(cue),
+            _ => sections.push(Section {
+                heading,
+                cues: vec![cue],
+            }),
+        }
+    }
+    Ok(sections)
+}
+
+/// Answer sheet as plain text (aligned columns), e.g. to print from a terminal.
+pub fn text(p: &Project) -> Result<String> {
+    let sections = sections(p)?;
+    let count: usize = sections.iter().map(|s| s.cues.len()).sum();
+    let w = count.to_string().len().max(2);
+
+    let mut out = format!("{} — {count} réponse(s)\n", sheet_title(p));
+    for s in &sections {
+        out.push('\n');
+        if let Some((title, at)) = s.heading {
+            out.push_str(&format!("{title} ({})\n", format_clock_ms(at)));
+        }
+        out.push_str(&format!(
+            "{:>w$}  {:<9}  {:<10}  Réponse\n",
+            "#", "Devinette", "Révélation"
+        ));
+        for c in &s.cues {
+            out.push_str(&format!(
+                "{:>w$}  {:<9}  {:<10}  {}\n",
+                c.number,
+                format_clock_ms(c.guess_ms),
+                format_clock_ms(c.reveal_ms),
+                answer_text(c)
+            ));
+        }
+    }
+    Ok(out)
+}
+
+/// Answer sheet as Markdown tables, one per round.
+pub fn markdown(p: &Project) -> Result<String> {
+    let escape = |s: &str| s.trim().replace('|', "\\|");
+
+    let mut out = format!("# {}\n", escape(sheet_title(p)));
+    for s in sections(p)? {
+        if let Some((title, at)) = s.heading {
+            out.push_str(&format!(
+                "\n## {} ({})\n",
+                escape(title),
+                format_clock_ms(at)
+            ));
+        }
+        out.push_str("\n| # | Devinette | Révélation | Réponse |\n|---:|---|---|---|\n");
+        for c in &s.cues {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                c.number,
+                format_clock_ms(c.guess_ms),
+                format_clock_ms(c.reveal_ms),
+                escape(&answer_text(c))
+            ));
+        }
+    }
+    Ok(out)
+}
+
+/// Answer sheet as CSV, with exact `HH:MM:SS.mmm` timestamps.
+///
+/// Structured answers also fill one column per field (empty for plain-text answers).
+pub fn csv(p: &Project) -> Result<String> {
+    let mut w = csv::Writer::from_writer(Vec::new());
+    w.write_record([
+        "number",
+        "round",
+        "guess_start",
+        "reveal_start",
+        "answer",
+        "artist",
+        "title",
+        "year",
+        "source",
+        "note",
+    ])?;
+    for s in sections(p)? {
+        let round = s.heading.map(|(title, _)| title).unwrap_or("");
+        for c in &s.cues {
+            let details = match &c.clip.answer {
+                Answer::Details(d) => d.clone(),
+                Answer::Text(_) => AnswerDetails::default(),
+            };
+            let field = |f: Option<String>| f.map(|f| f.trim().to_string()).unwrap_or_default();
+            w.write_record([
+                c.number.to_string(),
+                round.to_string(),
+                format_timecode_ms(c.guess_ms),
+                format_timecode_ms(c.reveal_ms),
+                c.clip.answer.summary(),
+                field(details.artist),
+                field(details.title),
+                field(details.year.map(|y| y.to_string())),
+                field(details.source),
+                field(details.note),
+            ])?;
+        }
+    }
+    Ok(String::from_utf8(w.into_inner()?)?)
+}
+
+/// Answer sheet as a standalone HTML page, styled for printing.
+pub fn html(p: &Project) -> Result<String> {
+    let title = escape_html(sheet_title(p));
+    let mut out = format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"fr\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n\
+         <style>\n\
+         body {{ font-family: sans-serif; margin: 2em; }}\n\
+         table {{ border-collapse: collapse; width: 100%; margin-bottom: 1.5em; }}\n\
+         th, td {{ border: 1px solid #999; padding: 4px 8px; text-align: left; }}\n\
+         td.n {{ text-align: right; width: 3em; }}\n\
+         tr {{ break-inside: avoid; }}\n\
+         h2 {{ break-after: avoid; }}\n\
+         </style>\n\
+         </head>\n\
+         <body>\n\
+         <h1>{title}</h1>\n"
+    );
+    for s in sections(p)? {
+        if let Some((round, at)) = s.heading {
+            out.push_str(&format!(
+                "<h2>{} <small>({})</small></h2>\n",
+                escape_html(round),
+                format_clock_ms(at)
+            ));
+        }
+        out.push_str(
+            "<table>\n<tr><th>#</th><th>Devinette</th><th>Révélation</th><th>Réponse</th></tr>\n",
+        );
+        for c in &s.cues {
+            out.push_str(&format!(
+                "<tr><td class=\"n\">{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                c.number,
+                format_clock_ms(c.guess_ms),
+                format_clock_ms(c.reveal_ms),
+                escape_html(&answer_text(c))
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    Ok(out)
+}
+
+/// Every field of the answer on one line, e.g. "Queen — Bohemian Rhapsody — 1975".
+fn answer_text(c: &ClipCue) -> String {
+    c.clip.answer.lines().join(" — ")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fixtures::{clip, intro, round, sample_project};
+
+    fn project() -> Project {
+        let mut p = sample_project();
+        p.intro = Some(intro("Soirée <80s>", "5s"));
+        p.clips[0].answer = "Queen | Live".into();
+        p.rounds = vec![round(
+            "Années 80",
+            vec![clip("videos/b.mp4", "0s", "A-ha, \"Take On Me\"")],
+        )];
+        p
+    }
+
+    #[test]
+    fn text_sheet_lists_answers_with_times() {
+        let out = text(&project()).unwrap();
+        assert_eq!(
+            out,
+            "Soirée <80s> — 2 réponse(s)\n\
+             \n \
+              #  Devinette  Révélation  Réponse\n \
+              1  00:05      00:15       Queen | Live\n\
+             \n\
+             Années 80 (00:20)\n \
+              #  Devinette  Révélation  Réponse\n \
+              2  00:23      00:33       A-ha, \"Take On Me\"\n"
+        );
+    }
+
+    #[test]
+    fn markdown_sheet_has_one_table_per_round() {
+        let out = markdown(&project()).unwrap();
+        assert!(out.starts_with("# Soirée <80s>\n"), "{out}");
+        assert!(
+            out.contains("| 1 | 00:05 | 00:15 | Queen \\| Live |\n"),
+            "{out}"
+        );
+        assert!(out.contains("\n## Années 80 (00:20)\n"), "{out}");
+    }
+
+    #[test]
+    fn csv_sheet_has_exact_timestamps() {
+        let out = csv(&project()).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "number,round,guess_start,reveal_start,answer,artist,title,year,source,note",
+                "1,,00:00:05.000,00:00:15.000,Queen | Live,,,,,",
+                "2,Années 80,00:00:23.000,00:00:33.000,\"A-ha, \"\"Take On Me\"\"\",,,,,",
+            ]
+        );
+    }
+
+    #[test]
+    fn structured_answers_fill_every_field() {
+        let mut p = project();
+        p.clips[0].answer = Answer::Details(AnswerDetails {
+            artist: Some("Queen".into()),
+            title: Some("Bohemian Rhapsody".into()),
+            year: Some(1975),
+            source: Some("Wayne's World".into()),
+            note: None,
+        });
+
+        let out = csv(&p).unwrap();
+        assert!(
+            out.contains(
+                "1,,00:00:05.000,00:00:15.000,Queen - Bohemian Rhapsody,Queen,Bohemian Rhapsody,1975,Wayne's World,\n"
+            ),
+            "{out}"
+        );
+        let out = markdown(&p).unwrap();
+        assert!(
+            out.contains("| Queen — Bohemian Rhapsody — 1975 · Wayne's World |"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn html_sheet_escapes_text() {
+        let out = html(&project()).unwrap();
+        assert!(out.contains("<h1>Soirée &lt;80s&gt;</h1>"), "{out}");
+        assert!(
+            out.contains("<td>A-ha, &quot;Take On Me&quot;</td>"),
+            "{out}"
+        );
+        assert!(out.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,283 @@
+// src/chapters.rs
+
+use anyhow::Result;
+
+use crate::model::Project;
+use crate::timecode::{format_clock_ms, format_timecode_ms};
+use crate::timeline::timeline;
+
+/// YouTube ignores every chapter if one of them is shorter than this.
+pub const YOUTUBE_MIN_CHAPTER_MS: u64 = 10_000;
+
+/// YouTube also ignores the list if it has fewer chapters than this.
+pub const YOUTUBE_MIN_CHAPTERS: usize = 3;
+
+/// One chapter of the rendered video.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub title: String,
+}
+
+/// Chapters of the rendered video: intro, one per round title card, one per
+/// clip (guess + reveal) and outro, in order.
+///
+/// Clips are titled "Extrait N" so the chapter list does not give the answers
+/// away, unless `with_answers` is set.
+pub fn chapters(p: &Project, with_answers: bool) -> Result<Vec<Chapter>> {
+    let t = timeline(p)?;
+    let mut chapters = Vec::new();
+    let or = |text: &str, default: &str| {
+        let text = text.trim();
+        if text.is_empty() { default } else { text }.to_string()
+    };
+
+    if let (Some(span), Some(intro)) = (t.intro, p.intro.as_ref()) {
+        chapters.push(Chapter {
+            start_ms: span.start_ms,
+            end_ms: span.end_ms,
+            title: or(&intro.title, "Introduction"),
+        });
+    }
+    for round in &t.rounds {
+        chapters.push(Chapter {
+            start_ms: round.title.start_ms,
+            end_ms: round.title.end_ms,
+            title: or(&round.round.title, "Manche"),
+        });
+    }
+    for cue in &t.clips {
+        let title = if with_answers {
+            format!("{}. {}", cue.number, one_line(&cue.clip.answer.summary()))
+        } else {
+            format!("Extrait {}", cue.number)
+        };
+        chapters.push(Chapter {
+            start_ms: cue.guess_ms,
+            end_ms: cue.end_ms,
+            title,
+        });
+    }
+    if let (Some(span), Some(outro)) = (t.outro, p.outro.as_ref()) {
+        chapters.push(Chapter {
+            start_ms: span.start_ms,
+            end_ms: span.end_ms,
+            title: or(&outro.text, "Fin"),
+        });
+    }
+
+    chapters.sort_by_key(|c| c.start_ms);
+    Ok(chapters)
+}
+
+/// FFmpeg metadata file (`;FFMETADATA1`) with one `[CHAPTER]` per chapter,
+/// to embed with `-i chapters.txt -map_chapters N`.
+pub fn ffmetadata(p: &Project, with_answers: bool) -> Result<String> {
+    let mut out = String::from(";FFMETADATA1\n");
+    if let Some(intro) = p.intro.as_ref().filter(|i| !i.title.trim().is_empty()) {
+        out.push_str(&format!(
+            "title={}\n",
+            escape_ffmetadata(intro.title.trim())
+        ));
+    }
+    for c in chapters(p, with_answers)? {
+        out.push_str(&format!(
+            "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            c.start_ms,
+            c.end_ms,
+            escape_ffmetadata(&c.title)
+        ));
+    }
+    Ok(out)
+}
+
+/// `=`, `;`, `#`, `\` and newlines are escaped with a backslash in ffmetadata values.
+fn escape_ffmetadata(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Chapter list for a YouTube description (`MM:SS Title` lines), see [`youtube_chapters`].
+pub fn youtube(p: &Project, with_answers: bool) -> Result<String> {
+    Ok(youtube_chapters(p, with_answers)?
+        .iter()
+        .map(|c| format!("{} {}\n", format_clock_ms(c.start_ms), c.title))
+        .collect())
+}
+
+/// Chapters as listed for YouTube.
+///
+/// Chapters shorter than [`YOUTUBE_MIN_CHAPTER_MS`] (intro, round title cards)
+/// are merged into the next one (at the end, the last chapter is extended instead).
+/// YouTube still ignores the list if fewer than [`YOUTUBE_MIN_CHAPTERS`] remain.
+pub fn youtube_chapters(p: &Project, with_answers: bool) -> Result<Vec<Chapter>> {
+    let merge = |a: Chapter, b: Chapter| Chapter {
+        start_ms: a.start_ms,
+        end_ms: b.end_ms,
+        title: format!("{} — {}", a.title, b.title),
+    };
+
+    let mut merged: Vec<Chapter> = Vec::new();
+    let mut pending: Option<Chapter> = None;
+    for c in chapters(p, with_answers)? {
+        let c = match pending.take() {
+            Some(short) => merge(short, c),
+            None => c,
+        };
+        if c.end_ms - c.start_ms < YOUTUBE_MIN_CHAPTER_MS {
+            pending = Some(c);
+        } else {
+            merged.push(c);
+        }
+    }
+    if let Some(short) = pending {
+        match merged.last_mut() {
+            Some(last) => last.end_ms = short.end_ms,
+            None => merged.push(short),
+        }
+    }
+
+    Ok(merged)
+}
+
+/// SubRip subtitles: each answer during its reveal window.
+pub fn srt(p: &Project) -> Result<String> {
+    let time = |ms: u64| format_timecode_ms(ms).replace('.', ",");
+    let mut out = String::new();
+    for cue in &timeline(p)?.clips {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            cue.number,
+            time(cue.reveal_ms),
+            time(cue.end_ms),
+            subtitle_text(&cue.clip.answer.lines().join("\n"))
+        ));
+    }
+    Ok(out)
+}
+
+/// WebVTT subtitles: each answer during its reveal window.
+pub fn webvtt(p: &Project) -> Result<String> {
+    let mut out = String::from("WEBVTT\n");
+    for cue in &timeline(p)?.clips {
+        let text = subtitle_text(&cue.clip.answer.lines().join("\n"))
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        out.push_str(&format!(
+            "\n{}\n{} --> {}\n{text}\n",
+            cue.number,
+            format_timecode_ms(cue.reveal_ms),
+            format_timecode_ms(cue.end_ms)
+        ));
+    }
+    Ok(out)
+}
+
+/// Subtitle text without blank lines (they would end the cue).
+fn subtitle_text(answer: &str) -> String {
+    let lines: Vec<&str> = answer
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    lines.join("\n")
+}
+
+fn one_line(answer: &str) -> String {
+    subtitle_text(answer).replace('\n', " — ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fixtures::{clip, intro, outro, round, sample_project};
+
+    fn project() -> Project {
+        let mut p = sample_project();
+        p.intro = Some(intro("Soirée", "5s"));
+        p.clips[0].answer = "Queen".into();
+        p.rounds = vec![round(
+            "Années 80",
+            vec![clip("videos/b.mp4", "0s", "A-ha <live> & co")],
+        )];
+        p.outro = Some(outro("", "4s"));
+        p
+    }
+
+    #[test]
+    fn lists_chapters_without_spoilers() {
+        let titles: Vec<_> = chapters(&project(), false)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.start_ms, c.end_ms, c.title))
+            .collect();
+        assert_eq!(
+            titles,
+            [
+                (0, 5_000, "Soirée".to_string()),
+                (5_000, 20_000, "Extrait 1".to_string()),
+                (20_000, 23_000, "Années 80".to_string()),
+                (23_000, 38_000, "Extrait 2".to_string()),
+                (38_000, 42_000, "Fin".to_string()),
+            ]
+        );
+
+        let with_answers = chapters(&project(), true).unwrap();
+        assert_eq!(with_answers[1].title, "1. Queen");
+    }
+
+    #[test]
+    fn writes_ffmetadata_chapters() {
+        let out = ffmetadata(&project(), false).unwrap();
+        assert!(out.starts_with(";FFMETADATA1\ntitle=Soirée\n"), "{out}");
+        assert!(
+            out.contains("[CHAPTER]\nTIMEBASE=1/1000\nSTART=20000\nEND=23000\ntitle=Années 80\n"),
+            "{out}"
+        );
+        assert_eq!(escape_ffmetadata("a=b;#\\"), "a\\=b\\;\\#\\\\");
+    }
+
+    #[test]
+    fn merges_short_chapters_for_youtube() {
+        assert_eq!(
+            youtube(&project(), false).unwrap(),
+            "00:00 Soirée — Extrait 1\n\
+             00:20 Années 80 — Extrait 2\n"
+        );
+    }
+
+    #[test]
+    fn counts_chapters_left_for_youtube() {
+        let mut p = project();
+        assert_eq!(youtube_chapters(&p, false).unwrap().len(), 2);
+
+        p.clips.push(p.clips[0].clone());
+        let chapters = youtube_chapters(&p, false).unwrap();
+        assert_eq!(chapters.len(), YOUTUBE_MIN_CHAPTERS);
+        assert_eq!(chapters[0].start_ms, 0);
+    }
+
+    #[test]
+    fn subtitles_cover_reveal_windows() {
+        assert_eq!(
+            srt(&project()).unwrap(),
+            "1\n00:00:15,000 --> 00:00:20,000\nQueen\n\n\
+             2\n00:00:33,000 --> 00:00:38,000\nA-ha <live> & co\n\n"
+        );
+        assert_eq!(
+            webvtt(&project()).unwrap(),
+            "WEBVTT\n\
+             \n1\n00:00:15.000 --> 00:00:20.000\nQueen\n\
+             \n2\n00:00:33.000 --> 00:00:38.000\nA-ha &lt;live&gt; &amp; co\n"
+        );
+    }
+}
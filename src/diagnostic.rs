@@ -0,0 +1,432 @@
+// src/diagnostic.rs
+
+use crate::validate::{Issue, Severity};
+
+/// 1-based line/column in a source file (column counted in characters).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Find where the value at `path` (e.g. `clips[3].answer`) is written in a JSON document.
+///
+/// When the exact value does not exist (e.g. a missing field), the closest
+/// existing parent is returned instead. Returns `None` for the empty path or
+/// if the document cannot be scanned.
+pub fn locate(source: &str, path: &str) -> Option<Position> {
+    let (start, _) = locate_span(source, path)?;
+    Some(position_at(source, start))
+}
+
+/// Byte span of the value at `path` (or of its closest existing parent).
+pub fn locate_span(source: &str, path: &str) -> Option<(usize, usize)> {
+    let mut segments = parse_path(path)?;
+    while !segments.is_empty() {
+        let mut scanner = Scanner {
+            s: source.as_bytes(),
+            pos: 0,
+        };
+        if let Some(span) = scanner.find(&segments) {
+            return Some(span);
+        }
+        segments.pop();
+    }
+    None
+}
+
+/// Position of a byte offset.
+pub fn position_at(source: &str, offset: usize) -> Position {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    Position { line, column }
+}
+
+/// Issue for a load error, positioned if the parser (JSON, YAML or TOML) tells where it failed.
+pub fn load_error_issue(err: &anyhow::Error, source: &str) -> Issue {
+    let positioned = |message: String, at: Position| Issue {
+        severity: Severity::Error,
+        path: String::new(),
+        message,
+        line: Some(at.line),
+        column: Some(at.column.max(1)),
+    };
+
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<serde_json::Error>()
+            && e.line() > 0
+        {
+            // serde_json appends " at line X column Y" to its messages
+            let full = e.to_string();
+            let suffix = format!(" at line {} column {}", e.line(), e.column());
+            let message = full.strip_suffix(&suffix).unwrap_or(&full);
+            // Data errors (wrong type, bad timecode, unknown field) come from valid JSON
+            let message = match e.classify() {
+                serde_json::error::Category::Data => message.to_string(),
+                _ => format!("invalid JSON: {message}"),
+            };
+            let at = Position {
+                line: e.line(),
+                column: e.column(),
+            };
+            return positioned(message, at);
+        }
+
+        if let Some(e) = cause.downcast_ref::<serde_yaml::Error>()
+            && let Some(loc) = e.location()
+        {
+            // Same suffix as serde_json (data errors are prefixed with their path)
+            let full = e.to_string();
+            let suffix = format!(" at line {} column {}", loc.line(), loc.column());
+            let message = full.strip_suffix(&suffix).unwrap_or(&full).to_string();
+            let at = Position {
+                line: loc.line(),
+                column: loc.column(),
+            };
+            return positioned(message, at);
+        }
+
+        if let Some(e) = cause.downcast_ref::<toml::de::Error>()
+            && let Some(span) = e.span()
+        {
+            // `message()` is the error alone, without the source excerpt of `Display`
+            let message = e.message().trim_end().to_string();
+            return positioned(message, position_at(source, span.start));
+        }
+    }
+
+    Issue {
+        severity: Severity::Error,
+        path: String::new(),
+        message: format!("{err:#}"),
+        line: None,
+        column: None,
+    }
+}
+
+/// Render an issue like a compiler diagnostic, with a source excerpt:
+///
+/// ```text
+/// error: clips[3].answer must not be empty
+///   --> montage.json:14:17
+///    |
+/// 14 |       "answer": "",
+///    |                 ^^
+/// ```
+pub fn render(issue: &Issue, file_name: &str, source: &str) -> String {
+    let level = match issue.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    };
+    let mut out = format!("{level}: {issue}\n");
+
+    let (Some(line), Some(column)) = (issue.line, issue.column) else {
+        out.push_str(&format!("  --> {file_name}\n"));
+        return out;
+    };
+
+    let Some(text) = source.lines().nth(line - 1) else {
+        out.push_str(&format!("  --> {file_name}:{line}:{column}\n"));
+        return out;
+    };
+
+    // Underline the value (up to the end of the line for multi-line values)
+    let width = match locate_span(source, &issue.path) {
+        Some((start, end)) if position_at(source, start).line == line => {
+            let value = &source[start..end];
+            value.lines().next().unwrap_or("").chars().count().max(1)
+        }
+        _ => 1,
+    };
+
+    let gutter = " ".repeat(line.to_string().len());
+    out.push_str(&format!("{gutter}--> {file_name}:{line}:{column}\n"));
+    out.push_str(&format!("{gutter} |\n"));
+    out.push_str(&format!("{line} | {text}\n"));
+    out.push_str(&format!(
+        "{gutter} | {}{}\n",
+        " ".repeat(column - 1),
+        "^".repeat(width)
+    ));
+    out
+}
+
+// --- JSON path scanning ---
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// "rounds[1].clips[0].answer" -> [Key(rounds), Index(1), Key(clips), Index(0), Key(answer)]
+fn parse_path(path: &str) -> Option<Vec<Segment>> {
+    if path.is_empty() {
+        return None;
+    }
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let (name, mut rest) = match part.find('[') {
+            Some(i) => (&part[..i], &part[i..]),
+            None => (part, ""),
+        };
+        if !name.is_empty() {
+            segments.push(Segment::Key(name.to_string()));
+        }
+        while let Some(r) = rest.strip_prefix('[') {
+            let (idx, tail) = r.split_once(']')?;
+            segments.push(Segment::Index(idx.parse().ok()?));
+            rest = tail;
+        }
+    }
+    Some(segments)
+}
+
+/// Minimal JSON scanner: walks the document without building values,
+/// to find the byte span of the value at a given path.
+struct Scanner<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn find(&mut self, path: &[Segment]) -> Option<(usize, usize)> {
+        self.skip_ws();
+        let Some((first, rest)) = path.split_first() else {
+            let start = self.pos;
+            self.skip_value()?;
+            return Some((start, self.pos));
+        };
+
+        match first {
+            Segment::Key(key) => {
+                self.expect(b'{')?;
+                loop {
+                    self.skip_ws();
+                    if self.peek()? == b'}' {
+                        return None;
+                    }
+                    let (ks, ke) = self.string_span()?;
+                    let name = &self.s[ks + 1..ke - 1];
+                    self.skip_ws();
+                    self.expect(b':')?;
+                    if name == key.as_bytes() {
+                        return self.find(rest);
+                    }
+                    self.skip_ws();
+                    self.skip_value()?;
+                    self.skip_ws();
+                    if self.peek()? == b',' {
+                        self.pos += 1;
+                    }
+                }
+            }
+            Segment::Index(n) => {
+                self.expect(b'[')?;
+                let mut idx = 0;
+                loop {
+                    self.skip_ws();
+                    if self.peek()? == b']' {
+                        return None;
+                    }
+                    if idx == *n {
+                        return self.find(rest);
+                    }
+                    self.skip_value()?;
+                    self.skip_ws();
+                    if self.peek()? == b',' {
+                        self.pos += 1;
+                    }
+                    idx += 1;
+                }
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Option<()> {
+        self.skip_ws();
+        (self.peek()? == c).then(|| self.pos += 1)
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Span of a string literal, quotes included.
+    fn string_span(&mut self) -> Option<(usize, usize)> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some((start, self.pos));
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => {
+                self.string_span()?;
+            }
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.pos += 1;
+                loop {
+                    self.skip_ws();
+                    match self.peek()? {
+                        c if c == close => {
+                            self.pos += 1;
+                            break;
+                        }
+                        b',' | b':' => self.pos += 1,
+                        _ => self.skip_value()?,
+                    }
+                }
+            }
+            _ => {
+                // number, true, false, null
+                let start = self.pos;
+                while let Some(c) = self.peek() {
+                    if matches!(c, b',' | b']' | b'}' | b' ' | b'\t' | b'\n' | b'\r') {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return None;
+                }
+            }
+        }
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"{
+  "output": { "path": "render/out.mp4", "fps": 30 },
+  "timings": { "guess_duration": "00:00:10.000", "reveal_duration": "00:00:05.000" },
+  "clips": [
+    { "video": "videos/a.mp4", "start": "00:00:01.000", "answer": "A \"quoted\"" },
+    {
+      "video": "videos/b.mp4",
+      "start": "banana",
+      "answer": ""
+    }
+  ],
+  "rounds": [
+    { "title": "Films", "clips": [ { "video": "c.mp4", "start": "00:00:00.000", "answer": "" } ] }
+  ]
+}"#;
+
+    #[test]
+    fn locates_values_by_json_path() {
+        assert_eq!(
+            locate(SOURCE, "output.fps"),
+            Some(Position {
+                line: 2,
+                column: 48
+            })
+        );
+        assert_eq!(
+            locate(SOURCE, "clips[1].start"),
+            Some(Position {
+                line: 8,
+                column: 16
+            })
+        );
+        assert_eq!(
+            locate(SOURCE, "rounds[0].clips[0].answer"),
+            Some(Position {
+                line: 13,
+                column: 91
+            })
+        );
+    }
+
+    #[test]
+    fn falls_back_to_closest_parent() {
+        // `end` is not written: point at the clip object itself
+        assert_eq!(
+            locate(SOURCE, "clips[1].end"),
+            Some(Position { line: 6, column: 5 })
+        );
+        assert_eq!(locate(SOURCE, ""), None);
+        assert_eq!(locate(SOURCE, "intro.title"), None);
+    }
+
+    #[test]
+    fn renders_source_excerpt() {
+        let issue = Issue {
+            severity: Severity::Error,
+            path: "clips[1].start".into(),
+            message: "is invalid: bad timecode".into(),
+            line: Some(8),
+            column: Some(16),
+        };
+
+        let out = render(&issue, "montage.json", SOURCE);
+        assert_eq!(
+            out,
+            "error: clips[1].start is invalid: bad timecode\n \
+--> montage.json:8:16\n  \
+|\n\
+8 |       \"start\": \"banana\",\n  \
+|                ^^^^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn positions_json_syntax_errors() {
+        let err = crate::parse_project("{\n  \"output\": {\n    \"path\": \"x.mp4\",\n  }\n}")
+            .unwrap_err();
+
+        let issue = load_error_issue(&err, "");
+        assert_eq!(issue.line, Some(4));
+        assert!(
+            issue.message.starts_with("invalid JSON: "),
+            "{}",
+            issue.message
+        );
+        assert!(!issue.message.contains(" at line "), "{}", issue.message);
+    }
+
+    #[test]
+    fn positions_yaml_and_toml_errors() {
+        use crate::{ProjectFormat, parse_project_as};
+
+        let yaml =
+            "output:\n  path: x.mp4\ntimings:\n  guess_duration: 10s\n  reveal_duration: banana\n";
+        let err = parse_project_as(yaml, ProjectFormat::Yaml).unwrap_err();
+        let issue = load_error_issue(&err, yaml);
+        // serde_yaml points at the enclosing mapping for value errors
+        assert_eq!(issue.line, Some(4), "{issue:?}");
+        assert!(
+            issue.message.contains("invalid timecode"),
+            "{}",
+            issue.message
+        );
+        assert!(!issue.message.contains(" at line "), "{}", issue.message);
+
+        let toml = "[output]\npath = \"x.mp4\"\nextra = 1\n\n[timings]\nguess_duration = \"10s\"\nreveal_duration = \"5s\"\n";
+        let err = parse_project_as(toml, ProjectFormat::Toml).unwrap_err();
+        let issue = load_error_issue(&err, toml);
+        assert_eq!((issue.line, issue.column), (Some(3), Some(1)), "{issue:?}");
+        assert!(issue.message.contains("unknown field"), "{}", issue.message);
+    }
+}
@@ -0,0 +1,38 @@
+// src/draw.rs
+
+use rand_chacha::ChaCha8Rng;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+
+/// Random stream for `seed`.
+///
+/// ChaCha8 is a fixed algorithm: unlike `StdRng`, the same seed gives the same
+/// numbers whatever the rand version or platform, so renders are reproducible.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Fisher-Yates shuffle drawing from `rng` only through `next_u64`
+/// (rand's `shuffle` and `gen_range` may change between versions).
+pub fn shuffle<T>(items: &mut [T], rng: &mut ChaCha8Rng) {
+    for i in (1..items.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_order() {
+        let mut items: Vec<u32> = (0..8).collect();
+        shuffle(&mut items, &mut seeded_rng(42));
+        // Pinned: a change here breaks every saved quiz and hint draw
+        assert_eq!(items, [5, 3, 2, 6, 7, 4, 0, 1]);
+
+        let mut again: Vec<u32> = (0..8).collect();
+        shuffle(&mut again, &mut seeded_rng(42));
+        assert_eq!(again, items);
+    }
+}
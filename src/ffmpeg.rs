@@ -2,28 +2,115 @@
 
 use crate::ffmpeg_command::CommandSpec;
 use anyhow::{Context, Result, bail};
+use std::io::{BufRead, BufReader, Read};
 use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Render progress reported by ffmpeg (`-progress pipe:1`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Position reached in the output video
+    pub out_time_ms: u64,
+    /// Expected duration of the output video
+    pub total_ms: u64,
+    /// Part of `out_time_ms` that was already done before this run
+    /// (e.g. cached segments), ignored by the ETA
+    pub skipped_ms: u64,
+    /// Encoding speed relative to real time (e.g. 2.5 => x2.5), if known
+    pub speed: Option<f64>,
+    /// Wall-clock time since ffmpeg started
+    pub elapsed: Duration,
+    /// True on the last report (`progress=end`)
+    pub done: bool,
+}
+
+impl Progress {
+    /// Completion ratio in `0.0..=1.0`.
+    pub fn fraction(&self) -> f64 {
+        if self.done {
+            return 1.0;
+        }
+        if self.total_ms == 0 {
+            return 0.0;
+        }
+        (self.out_time_ms as f64 / self.total_ms as f64).clamp(0.0, 1.0)
+    }
+
+    /// Estimated remaining time, extrapolated from the elapsed time.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.done {
+            return Some(Duration::ZERO);
+        }
+        let done = self.out_time_ms.saturating_sub(self.skipped_ms);
+        let todo = self.total_ms.saturating_sub(self.skipped_ms);
+        if done == 0 || todo == 0 {
+            return None;
+        }
+        let f = (done as f64 / todo as f64).min(1.0);
+        Some(self.elapsed.mul_f64((1.0 - f) / f))
+    }
+}
 
 /// Run the provided ffmpeg command spec.
 ///
 /// - Captures stderr (ffmpeg writes progress/errors there)
 /// - Returns an error if exit status != 0
 pub fn run(spec: &CommandSpec) -> Result<()> {
+    run_with_progress(spec, 0, |_| {})
+}
+
+/// Run the provided ffmpeg command spec, reporting progress.
+///
+/// - Adds `-progress pipe:1 -nostats` so ffmpeg writes `key=value` reports on stdout
+/// - Calls `on_progress` after each report, `total_ms` being the expected
+///   duration of the output (see `ffmpeg_command::expected_duration_ms`)
+/// - Captures stderr, shown if exit status != 0
+pub fn run_with_progress<F>(spec: &CommandSpec, total_ms: u64, mut on_progress: F) -> Result<()>
+where
+    F: FnMut(&Progress),
+{
     let mut cmd = Command::new(&spec.program);
-    cmd.args(&spec.args)
+    cmd.args(["-progress", "pipe:1", "-nostats"])
+        .args(&spec.args)
         .stdin(Stdio::null())
-        .stdout(Stdio::null())
+        .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
-    let output = cmd
-        .output()
+    let started = Instant::now();
+    let mut child = cmd
+        .spawn()
         .with_context(|| format!("failed to spawn {}", spec.program))?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    // stderr is drained on its own thread so ffmpeg never blocks on a full pipe
+    let mut stderr_pipe = child.stderr.take().context("ffmpeg stderr not captured")?;
+    let stderr_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        stderr_pipe.read_to_end(&mut buf).ok();
+        buf
+    });
+
+    let stdout = child.stdout.take().context("ffmpeg stdout not captured")?;
+    let mut parser = ProgressParser::new(total_ms);
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if let Some(progress) = parser.feed(&line, started.elapsed()) {
+            on_progress(&progress);
+        }
+    }
+
+    let status = child
+        .wait()
+        .with_context(|| format!("failed to wait for {}", spec.program))?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
         bail!(
             "ffmpeg failed (exit code {:?}). stderr:\n{}",
-            output.status.code(),
+            status.code(),
             stderr
         );
     }
@@ -31,6 +118,60 @@ pub fn run(spec: &CommandSpec) -> Result<()> {
     Ok(())
 }
 
+/// Incremental parser of ffmpeg `-progress` output.
+///
+/// ffmpeg writes blocks of `key=value` lines, each block ending with
+/// `progress=continue` (or `progress=end` for the last one).
+#[derive(Debug, Clone)]
+pub struct ProgressParser {
+    total_ms: u64,
+    out_time_ms: u64,
+    speed: Option<f64>,
+}
+
+impl ProgressParser {
+    pub fn new(total_ms: u64) -> Self {
+        Self {
+            total_ms,
+            out_time_ms: 0,
+            speed: None,
+        }
+    }
+
+    /// Feed one line; returns a report when a block is complete.
+    pub fn feed(&mut self, line: &str, elapsed: Duration) -> Option<Progress> {
+        let (key, value) = line.trim().split_once('=')?;
+        let value = value.trim();
+
+        match key {
+            // Despite its name, `out_time_ms` is in microseconds (like `out_time_us`)
+            "out_time_us" | "out_time_ms" => {
+                if let Ok(us) = value.parse::<i64>() {
+                    self.out_time_ms = (us.max(0) as u64) / 1000;
+                }
+            }
+            "speed" => {
+                self.speed = value
+                    .strip_suffix('x')
+                    .and_then(|v| v.trim().parse::<f64>().ok());
+            }
+            "progress" => {
+                return Some(Progress {
+                    out_time_ms: self.out_time_ms,
+                    total_ms: self.total_ms,
+                    skipped_ms: 0,
+                    speed: self.speed,
+                    elapsed,
+                    done: value == "end",
+                });
+            }
+            _ => {}
+        }
+
+        None
+    }
+}
+
 /// Format the command as a shell-like string for display/debugging.
 /// (We do minimal quoting so spaces are readable.)
 pub fn format_command(spec: &CommandSpec) -> String {
@@ -53,3 +194,72 @@ fn quote_arg(arg: &str) -> String {
         arg.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed_all(parser: &mut ProgressParser, block: &str) -> Vec<Progress> {
+        block
+            .lines()
+            .filter_map(|l| parser.feed(l, Duration::from_secs(10)))
+            .collect()
+    }
+
+    #[test]
+    fn parses_progress_blocks() {
+        let mut parser = ProgressParser::new(60_000);
+        let reports = feed_all(
+            &mut parser,
+            "frame=300\nfps=30.00\nout_time_us=15000000\nout_time_ms=15000000\n\
+out_time=00:00:15.000000\nspeed=1.5x\nprogress=continue\n",
+        );
+
+        assert_eq!(reports.len(), 1);
+        let p = reports[0];
+        assert_eq!(p.out_time_ms, 15_000);
+        assert_eq!(p.speed, Some(1.5));
+        assert!(!p.done);
+        assert!((p.fraction() - 0.25).abs() < 1e-9);
+        // 25% done in 10s => 30s left
+        assert_eq!(p.eta(), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn handles_unknown_speed_and_end() {
+        let mut parser = ProgressParser::new(60_000);
+        let reports = feed_all(
+            &mut parser,
+            "out_time_us=N/A\nspeed=N/A\nprogress=continue\nout_time_us=59000000\nprogress=end\n",
+        );
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].out_time_ms, 0);
+        assert_eq!(reports[0].speed, None);
+        assert_eq!(reports[0].eta(), None);
+        assert!(reports[1].done);
+        assert_eq!(reports[1].fraction(), 1.0);
+    }
+
+    #[test]
+    fn clamps_fraction() {
+        let mut parser = ProgressParser::new(1_000);
+        let reports = feed_all(&mut parser, "out_time_us=5000000\nprogress=continue\n");
+        assert_eq!(reports[0].fraction(), 1.0);
+    }
+
+    #[test]
+    fn eta_ignores_skipped_time() {
+        let p = Progress {
+            out_time_ms: 40_000,
+            total_ms: 60_000,
+            skipped_ms: 30_000,
+            speed: None,
+            elapsed: Duration::from_secs(10),
+            done: false,
+        };
+        assert!((p.fraction() - 2.0 / 3.0).abs() < 1e-9);
+        // 10s rendered in 10s, 20s left to render
+        assert_eq!(p.eta(), Some(Duration::from_secs(20)));
+    }
+}
@@ -1,9 +1,13 @@
 // src/ffmpeg_command.rs
 
-use crate::model::Project;
-use crate::timecode::parse_timecode_ms;
-use anyhow::{Result, bail};
-use std::path::PathBuf;
+use crate::hint::hint_steps;
+use crate::model::{Clip, Project, Round, Timings};
+use crate::quiz::{OPTION_SPACING, QuizChoices, quiz_choices};
+use crate::theme::{Look, TextLook, resolve_look};
+use anyhow::{Context, Result, bail};
+
+/// Default duration of a round title card (3 seconds).
+pub const DEFAULT_ROUND_TITLE_MS: u64 = 3_000;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CommandSpec {
@@ -11,14 +15,105 @@ pub struct CommandSpec {
     pub args: Vec<String>, // argv
 }
 
+/// Guess/hint/reveal durations of one clip, once per-clip overrides are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipTimings {
+    pub guess_ms: u64,
+    /// Hint phase between guess and reveal (0 when there is none)
+    pub hint_ms: u64,
+    pub reveal_ms: u64,
+}
+
+impl ClipTimings {
+    /// Length of the whole clip window (guess + hint + reveal).
+    pub fn total_ms(&self) -> u64 {
+        self.guess_ms + self.hint_ms + self.reveal_ms
+    }
+}
+
+/// Resolve the effective timings of a clip.
+///
+/// - `clip.guess_duration` overrides `timings.guess_duration`
+/// - `clip.hint_duration` overrides `timings.hint_duration` (no hint phase if both are unset)
+/// - `clip.reveal_duration` overrides `timings.reveal_duration`
+/// - `clip.end` makes the reveal last until that timecode in the source
+///
+/// `fps` is the output frame rate, used by SMPTE (`HH:MM:SS:FF`) timecodes.
+pub fn resolve_clip_timings(clip: &Clip, timings: &Timings, fps: u32) -> Result<ClipTimings> {
+    let guess_ms = clip
+        .guess_duration
+        .unwrap_or(timings.guess_duration)
+        .as_ms(fps);
+    let hint_ms = clip
+        .hint_duration
+        .or(timings.hint_duration)
+        .map_or(0, |h| h.as_ms(fps));
+
+    let reveal_ms = match (clip.end, clip.reveal_duration) {
+        (Some(_), Some(_)) => bail!("clip end and reveal_duration cannot be both set"),
+        (Some(end), None) => end
+            .as_ms(fps)
+            .checked_sub(clip.start.as_ms(fps) + guess_ms + hint_ms)
+            .context("clip end must be after start + guess_duration + hint_duration")?,
+        (None, Some(r)) => r.as_ms(fps),
+        (None, None) => timings.reveal_duration.as_ms(fps),
+    };
+
+    if guess_ms == 0 || reveal_ms == 0 {
+        bail!("guess_duration and reveal_duration must be > 0");
+    }
+    if clip.hint.is_some() && hint_ms == 0 {
+        bail!("clip hint needs a hint_duration > 0");
+    }
+
+    Ok(ClipTimings {
+        guess_ms,
+        hint_ms,
+        reveal_ms,
+    })
+}
+
+/// Expected duration of the rendered video:
+/// intro + round title cards + every clip (guess + reveal) + outro.
+pub fn expected_duration_ms(p: &Project) -> Result<u64> {
+    Ok(crate::timeline::timeline(p)?.total_ms)
+}
+
+/// Embed the chapters of an ffmetadata file (see [`crate::chapters::ffmetadata`])
+/// into the output of an ffmpeg command: the file is added as the last input
+/// and its metadata and chapters are mapped to the output.
+pub fn embed_chapters(spec: &mut CommandSpec, metadata_path: &str) {
+    let index = spec.args.iter().filter(|a| *a == "-i").count();
+    let after_inputs = spec
+        .args
+        .iter()
+        .rposition(|a| a == "-i")
+        .map_or(0, |i| i + 2);
+    spec.args.splice(
+        after_inputs..after_inputs,
+        ["-f", "ffmetadata", "-i", metadata_path].map(String::from),
+    );
+
+    // Output options go right before the output path (last argument)
+    let output = spec.args.len().saturating_sub(1);
+    let index = index.to_string();
+    spec.args.splice(
+        output..output,
+        ["-map_metadata", &index, "-map_chapters", &index].map(String::from),
+    );
+}
+
 /// Build a single-ffmpeg command (Option B) using `-filter_complex`.
 ///
-/// Pipeline per clip:
-/// - trim total (guess+reveal) from `start`
+/// Pipeline per clip (durations resolved with [`resolve_clip_timings`]):
+/// - input seeking: `-ss start -t total -i video` (ffmpeg jumps to `start`
+///   instead of decoding the source from 00:00)
+/// - reset timestamps so the clip starts at 0
 /// - split audio
 /// - guess: black screen + countdown (seconds), audio kept
 /// - reveal: video + answer overlay, audio kept
 /// - concat guess+reveal
+///
 /// Then concat all clips into [vout][aout].
 ///
 /// Optional intro (if present):
@@ -26,63 +121,88 @@ pub struct CommandSpec {
 /// - input 1: intro music audio
 /// - build [vintro][aintro] for `intro.duration`
 /// - final concat becomes: intro + clips
+///
+/// Optional rounds (if present):
+/// - one looped image input per round background, after the clip inputs
+/// - build [vround{r}][around{r}] title card (background or black screen, silent audio)
+/// - final concat becomes: intro + top-level clips + (title card + round clips)...
+///
+/// Optional outro (if present):
+/// - looped background image + outro music, after all other inputs
+/// - build [voutro][aoutro] for `outro.duration`
+/// - appended at the end of the final concat
 pub fn build_ffmpeg_command(p: &Project) -> Result<CommandSpec> {
     // Defaults (V1): if output params missing, pick deterministic values
-    let (w, h) = parse_resolution(p.output.resolution.as_deref().unwrap_or("1920x1080"))?;
-    let fps = p.output.fps.unwrap_or(30);
-
-    let guess_ms = parse_timecode_ms(p.timings.guess_duration.trim())?;
-    let reveal_ms = parse_timecode_ms(p.timings.reveal_duration.trim())?;
-    if guess_ms == 0 || reveal_ms == 0 {
-        bail!("guess_duration and reveal_duration must be > 0");
-    }
-
-    let guess_s = ms_to_seconds_f64(guess_ms);
-    let reveal_s = ms_to_seconds_f64(reveal_ms);
-    let total_s = guess_s + reveal_s;
-
-    // Optional intro duration
-    let intro_s: Option<f64> = p
-        .intro
-        .as_ref()
-        .map(|i| parse_timecode_ms(i.duration.trim()))
-        .transpose()?
-        .map(ms_to_seconds_f64);
+    let canvas = Canvas::from_project(p)?;
 
     // Build inputs: if intro present -> 2 extra inputs at beginning
     let mut args: Vec<String> = Vec::new();
     args.push("-y".into());
 
-    let clip_base: usize;
-
-    if let Some(intro) = p.intro.as_ref() {
+    let clip_base: usize = if let Some(intro) = p.intro.as_ref() {
         // Input 0: looped image
-        args.push("-loop".into());
-        args.push("1".into());
-        args.push("-i".into());
-        args.push(intro.background.trim().to_string());
+        push_looped_image(&mut args, &intro.background);
 
         // Input 1: intro music
         args.push("-i".into());
         args.push(intro.music.trim().to_string());
 
-        clip_base = 2;
+        2
     } else {
-        clip_base = 0;
+        0
+    };
+
+    // Clip inputs (top-level clips first, then each round's clips).
+    // Seeking is done on the input side: `-ss`/`-t` before `-i`.
+    let mut clip_count = 0;
+    for (at, c) in p.clip_paths() {
+        let timings = resolve_clip_timings(c, &p.timings, p.fps())
+            .with_context(|| format!("{at} has invalid timings"))?;
+        push_clip_input(&mut args, c, timings, p.fps());
+        clip_count += 1;
     }
 
-    // Clip inputs
-    let mut clip_inputs: Vec<PathBuf> = Vec::with_capacity(p.clips.len());
-    for c in &p.clips {
-        clip_inputs.push(PathBuf::from(c.video.trim()));
+    // Round background inputs (looped images), after the clips
+    let mut next_input = clip_base + clip_count;
+    let mut round_inputs: Vec<Option<usize>> = Vec::with_capacity(p.rounds.len());
+    for round in &p.rounds {
+        match round.background.as_deref() {
+            Some(bg) => {
+                push_looped_image(&mut args, bg);
+                round_inputs.push(Some(next_input));
+                next_input += 1;
+            }
+            None => round_inputs.push(None),
+        }
     }
-    for input in &clip_inputs {
+
+    // Outro inputs: looped image + music
+    let outro_base = p.outro.as_ref().map(|outro| {
+        push_looped_image(&mut args, &outro.background);
+
         args.push("-i".into());
-        args.push(input.to_string_lossy().to_string());
+        args.push(outro.music.trim().to_string());
+
+        next_input
+    });
+    if outro_base.is_some() {
+        next_input += 2;
     }
 
-    let filter_complex =
-        build_filter_complex(p, clip_base, w, h, fps, guess_s, reveal_s, total_s, intro_s)?;
+    // Theme guess image, last: decoded once, shared by every guess/hint screen
+    let look = resolve_look(p)?;
+    let guess_image = look.guess_image.as_deref().map(|image| {
+        push_image(&mut args, image);
+        next_input
+    });
+
+    let layout = InputLayout {
+        clip_base,
+        round_inputs,
+        outro_base,
+        guess_image,
+    };
+    let filter_complex = build_filter_complex(p, &layout, canvas, &look)?;
 
     args.push("-filter_complex".into());
     args.push(filter_complex);
@@ -93,13 +213,7 @@ pub fn build_ffmpeg_command(p: &Project) -> Result<CommandSpec> {
     args.push("-map".into());
     args.push("[aout]".into());
 
-    // Encoding (simple V1)
-    args.push("-c:v".into());
-    args.push("libx264".into());
-    args.push("-pix_fmt".into());
-    args.push("yuv420p".into());
-    args.push("-c:a".into());
-    args.push("aac".into());
+    push_encoding_args(&mut args);
 
     // Output path
     args.push(p.output.path.trim().to_string());
@@ -110,119 +224,496 @@ pub fn build_ffmpeg_command(p: &Project) -> Result<CommandSpec> {
     })
 }
 
+/// Output frame size and rate shared by every part of the video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Canvas {
+    pub w: u32,
+    pub h: u32,
+    pub fps: u32,
+}
+
+impl Canvas {
+    /// Output parameters of the project, with the V1 defaults (1920x1080, 30 fps).
+    pub(crate) fn from_project(p: &Project) -> Result<Self> {
+        let (w, h) = parse_resolution(p.output.resolution.as_deref().unwrap_or("1920x1080"))?;
+        let fps = p.fps();
+        Ok(Self { w, h, fps })
+    }
+}
+
+/// Input indexes of the ffmpeg command, as used by the filter graph.
+struct InputLayout {
+    /// Index of the first clip video (the others follow, in playing order)
+    clip_base: usize,
+    /// Background input of each round (None => black screen)
+    round_inputs: Vec<Option<usize>>,
+    /// Index of the outro background (music is the next input)
+    outro_base: Option<usize>,
+    /// Single frame of `theme.guess_image`
+    guess_image: Option<usize>,
+}
+
 fn build_filter_complex(
     p: &Project,
-    clip_base: usize,
-    w: u32,
-    h: u32,
-    fps: u32,
-    guess_s: f64,
-    reveal_s: f64,
-    total_s: f64,
-    intro_s: Option<f64>,
+    layout: &InputLayout,
+    canvas: Canvas,
+    look: &Look,
 ) -> Result<String> {
     let mut parts: Vec<String> = Vec::new();
 
     // Optional intro segment labels
     let mut has_intro = false;
-    if let (Some(intro), Some(intro_s)) = (p.intro.as_ref(), intro_s) {
+    if let Some(intro) = p.intro.as_ref() {
         has_intro = true;
 
-        let title = escape_drawtext_text(intro.title.trim());
-        // Build intro video from looped image input #0
-        // Note: we trim to duration and reset timestamps
-        parts.push(format!(
-            "[0:v]scale={w}:{h},fps={fps},setsar=1,trim=duration={intro_s:.3},setpts=PTS-STARTPTS,\
-drawtext=text='{title}':x=(w-text_w)/2:y=(h-text_h)/2:fontsize=72:fontcolor=white:borderw=4[vintro]"
+        let intro_s = ms_to_seconds_f64(intro.duration.as_ms(canvas.fps));
+        // Intro video from looped image input #0, audio from input #1
+        parts.extend(image_card_filters(
+            canvas,
+            look,
+            0,
+            1,
+            &intro.title,
+            intro_s,
+            "intro",
         ));
+    }
 
-        // Intro audio from input #1
-        parts.push(format!(
-            "[1:a]atrim=0:{intro_s:.3},asetpts=PTS-STARTPTS[aintro]"
+    // Round title cards
+    for (r, round) in p.rounds.iter().enumerate() {
+        let dur_s = round_title_seconds(round, canvas.fps);
+        let bg = layout.round_inputs.get(r).copied().flatten();
+        parts.extend(round_card_filters(
+            canvas,
+            look,
+            bg,
+            &round.title,
+            dur_s,
+            &format!("round{r}"),
         ));
     }
 
     // Per-clip pipeline
-    for (i, clip) in p.clips.iter().enumerate() {
-        let input_index = clip_base + i;
-
-        let start_ms = parse_timecode_ms(clip.start.trim())?;
-        let start_s = ms_to_seconds_f64(start_ms);
-
-        // Labels
-        let v_all = format!("[v{i}all]");
-        let a_all = format!("[a{i}all]");
-        let a_gsrc = format!("[a{i}gsrc]");
-        let a_rsrc = format!("[a{i}rsrc]");
-        let v_g = format!("[v{i}g]");
-        let a_g = format!("[a{i}g]");
-        let v_r = format!("[v{i}r]");
-        let a_r = format!("[a{i}r]");
-        let v_i = format!("[v{i}]");
-        let a_i = format!("[a{i}]");
-
-        // 1) Trim + normalize video
-        parts.push(format!(
-            "[{input_index}:v]trim=start={start_s:.3}:duration={total_s:.3},setpts=PTS-STARTPTS,\
-scale={w}:{h},fps={fps},setsar=1{v_all}",
+    let choices = quiz_choices(p)?;
+    let mut screens: Vec<String> = Vec::new();
+    for (i, (at, clip)) in p.clip_paths().into_iter().enumerate() {
+        let timings = resolve_clip_timings(clip, &p.timings, p.fps())
+            .with_context(|| format!("{at} has invalid timings"))?;
+        screens.extend(guess_image_labels(i, timings));
+        parts.extend(clip_filters(
+            canvas,
+            look,
+            i,
+            layout.clip_base + i,
+            clip,
+            timings,
+            choices[i].as_ref(),
         ));
+    }
+    if let Some(input) = layout.guess_image {
+        parts.push(guess_image_filter(canvas, input, &screens));
+    }
 
-        // 2) Trim audio
-        parts.push(format!(
-            "[{input_index}:a]atrim=start={start_s:.3}:duration={total_s:.3},asetpts=PTS-STARTPTS{a_all}",
+    // Final concat
+    let mut concat_in = String::new();
+    let mut n = 0;
+
+    if has_intro {
+        concat_in.push_str("[vintro][aintro]");
+        n += 1;
+    }
+
+    let mut clip_idx = 0;
+    for _ in &p.clips {
+        concat_in.push_str(&format!("[v{clip_idx}][a{clip_idx}]"));
+        clip_idx += 1;
+        n += 1;
+    }
+
+    for (r, round) in p.rounds.iter().enumerate() {
+        concat_in.push_str(&format!("[vround{r}][around{r}]"));
+        n += 1;
+        for _ in &round.clips {
+            concat_in.push_str(&format!("[v{clip_idx}][a{clip_idx}]"));
+            clip_idx += 1;
+            n += 1;
+        }
+    }
+
+    if let (Some(outro), Some(idx)) = (p.outro.as_ref(), layout.outro_base) {
+        let outro_s = ms_to_seconds_f64(outro.duration.as_ms(canvas.fps));
+        parts.extend(image_card_filters(
+            canvas,
+            look,
+            idx,
+            idx + 1,
+            &outro.text,
+            outro_s,
+            "outro",
         ));
 
-        // 3) Split audio
+        concat_in.push_str("[voutro][aoutro]");
+        n += 1;
+    }
+
+    parts.push(format!("{concat_in}concat=n={n}:v=1:a=1[vout][aout]"));
+
+    Ok(parts.join(";"))
+}
+
+/// Filters of an image card (intro/outro): looped image `bg` + music `music`
+/// with centered text, producing `[v{label}][a{label}]`.
+pub(crate) fn image_card_filters(
+    canvas: Canvas,
+    look: &Look,
+    bg: usize,
+    music: usize,
+    text: &str,
+    dur_s: f64,
+    label: &str,
+) -> Vec<String> {
+    let Canvas { w, h, fps } = canvas;
+    let text = drawtext(
+        &look.title,
+        &escape_drawtext_text(text.trim()),
+        &text_y(&look.title, "(h-text_h)/2"),
+    );
+
+    // Note: we trim to duration and reset timestamps
+    vec![
+        format!(
+            "[{bg}:v]scale={w}:{h},fps={fps},setsar=1,trim=duration={dur_s:.3},setpts=PTS-STARTPTS,\
+{text}[v{label}]"
+        ),
+        format!("[{music}:a]atrim=0:{dur_s:.3},asetpts=PTS-STARTPTS[a{label}]"),
+    ]
+}
+
+/// Filters of a round title card (background image or theme background colour,
+/// silent audio), producing `[v{label}][a{label}]`.
+pub(crate) fn round_card_filters(
+    canvas: Canvas,
+    look: &Look,
+    bg: Option<usize>,
+    title: &str,
+    dur_s: f64,
+    label: &str,
+) -> Vec<String> {
+    let Canvas { w, h, fps } = canvas;
+    let title = drawtext(
+        &look.title,
+        &escape_drawtext_text(title.trim()),
+        &text_y(&look.title, "(h-text_h)/2"),
+    );
+
+    let background = match bg {
+        Some(idx) => format!(
+            "[{idx}:v]scale={w}:{h},fps={fps},setsar=1,trim=duration={dur_s:.3},setpts=PTS-STARTPTS"
+        ),
+        None => format!(
+            "color=c={}:s={w}x{h}:r={fps}:d={dur_s:.3},setsar=1",
+            look.background
+        ),
+    };
+
+    vec![
+        format!("{background},{title}[v{label}]"),
+        // Silent audio for the title card
+        format!("anullsrc=r=44100:cl=stereo,atrim=0:{dur_s:.3},asetpts=PTS-STARTPTS[a{label}]"),
+    ]
+}
+
+/// Labels of the `theme.guess_image` copies used by clip `i`:
+/// its guess screen, then its hint screen.
+pub(crate) fn guess_image_labels(i: usize, timings: ClipTimings) -> Vec<String> {
+    let mut labels = vec![format!("[v{i}gimg]")];
+    if timings.hint_ms > 0 {
+        labels.push(format!("[v{i}himg]"));
+    }
+    labels
+}
+
+/// Decode the theme guess image (single frame `input`) once and split it
+/// into `labels`, one still frame per screen.
+pub(crate) fn guess_image_filter(canvas: Canvas, input: usize, labels: &[String]) -> String {
+    let Canvas { w, h, .. } = canvas;
+    format!(
+        "[{input}:v]scale={w}:{h},setsar=1,split={}{}",
+        labels.len(),
+        labels.concat()
+    )
+}
+
+/// Filters of clip `i`, producing `[v{i}][a{i}]`.
+///
+/// With `choices` (quiz mode), the options are listed under the countdown
+/// and the reveal shows the correct one highlighted.
+pub(crate) fn clip_filters(
+    canvas: Canvas,
+    look: &Look,
+    i: usize,
+    input_index: usize,
+    clip: &Clip,
+    timings: ClipTimings,
+    choices: Option<&QuizChoices>,
+) -> Vec<String> {
+    let Canvas { w, h, fps } = canvas;
+    let guess_s = ms_to_seconds_f64(timings.guess_ms);
+    let hint_s = ms_to_seconds_f64(timings.hint_ms);
+    let reveal_s = ms_to_seconds_f64(timings.reveal_ms);
+    let reveal_from_s = ms_to_seconds_f64(timings.guess_ms + timings.hint_ms);
+    let has_hint = timings.hint_ms > 0;
+
+    // Guess/hint screens: still of the theme image repeated, or plain colour
+    let images = guess_image_labels(i, timings);
+    let screen = |phase: usize, dur_s: f64| match look.guess_image {
+        Some(_) => format!(
+            "{}loop=loop=-1:size=1,setpts=N/({fps}*TB),fps={fps},trim=duration={dur_s:.3}",
+            images[phase]
+        ),
+        None => format!("color=c={}:s={w}x{h}:r={fps}:d={dur_s:.3}", look.background),
+    };
+
+    // Labels
+    let v_all = format!("[v{i}all]");
+    let a_all = format!("[a{i}all]");
+    let a_gsrc = format!("[a{i}gsrc]");
+    let a_hsrc = format!("[a{i}hsrc]");
+    let a_rsrc = format!("[a{i}rsrc]");
+    let v_g = format!("[v{i}g]");
+    let a_g = format!("[a{i}g]");
+    let v_h = format!("[v{i}h]");
+    let a_h = format!("[a{i}h]");
+    let v_r = format!("[v{i}r]");
+    let a_r = format!("[a{i}r]");
+    let v_i = format!("[v{i}]");
+    let a_i = format!("[a{i}]");
+
+    let mut parts = Vec::new();
+
+    // 1) Normalize video (already seeked/limited by the input options)
+    parts.push(format!(
+        "[{input_index}:v]setpts=PTS-STARTPTS,scale={w}:{h},fps={fps},setsar=1{v_all}"
+    ));
+
+    // 2) Audio, timestamps reset to 0
+    parts.push(format!("[{input_index}:a]asetpts=PTS-STARTPTS{a_all}"));
+
+    // 3) Split audio
+    if has_hint {
+        parts.push(format!("{a_all}asplit=3{a_gsrc}{a_hsrc}{a_rsrc}"));
+    } else {
         parts.push(format!("{a_all}asplit=2{a_gsrc}{a_rsrc}"));
+    }
 
-        // 4) Guess video: black screen + countdown (seconds)
-        let countdown_text = format!("%{{eif\\:max(0\\,ceil({guess_s:.3}-t))\\:d}}");
-        parts.push(format!(
-            "color=c=black:s={w}x{h}:r={fps}:d={guess_s:.3},\
-drawtext=text='{countdown_text}':\
-x=(w-text_w)/2:y=(h-text_h)/2:\
-fontsize=96:fontcolor=white:borderw=4{v_g}"
-        ));
+    // 4) Guess video: screen + countdown (seconds),
+    //    moved to the top when the quiz options fill the screen
+    let countdown_text = format!("%{{eif\\:max(0\\,ceil({guess_s:.3}-t))\\:d}}");
+    let countdown_y = if choices.is_some() {
+        "h/6-text_h/2"
+    } else {
+        "(h-text_h)/2"
+    };
+    let mut guess = format!(
+        "{},{}",
+        screen(0, guess_s),
+        drawtext(
+            &look.countdown,
+            &countdown_text,
+            &text_y(&look.countdown, countdown_y)
+        )
+    );
+    if let Some(choices) = choices {
+        // Options on evenly spaced lines over the lower two thirds
+        for o in 0..choices.options.len() {
+            let line = escape_drawtext_text(&choices.line(o));
+            let step = OPTION_SPACING * o as u32;
+            let y = match look.options.y {
+                Some(y) => format!("h*{}/100-text_h/2", y + step),
+                None => format!("h*{}/100", 40 + step),
+            };
+            guess.push(',');
+            guess.push_str(&drawtext(&look.options, &line, &y));
+        }
+    }
+    parts.push(format!("{guess}{v_g}"));
 
-        // Guess audio: first segment [0, guess]
-        parts.push(format!(
-            "{a_gsrc}atrim=0:{guess_s:.3},asetpts=PTS-STARTPTS{a_g}"
-        ));
+    // Guess audio: first segment [0, guess]
+    parts.push(format!(
+        "{a_gsrc}atrim=0:{guess_s:.3},asetpts=PTS-STARTPTS{a_g}"
+    ));
+
+    // 5) Optional hint video: screen + hint text, one drawtext per
+    //    hangman step (enabled during its own time window)
+    if has_hint {
+        let steps = hint_steps(clip, timings.hint_ms);
+        let mut hint = screen(1, hint_s);
+        for step in &steps {
+            let text = escape_drawtext_text(&step.text);
+            let enable = if steps.len() > 1 {
+                format!(
+                    ":enable='gte(t,{:.3})*lt(t,{:.3})'",
+                    ms_to_seconds_f64(step.start_ms),
+                    ms_to_seconds_f64(step.end_ms)
+                )
+            } else {
+                String::new()
+            };
+            let y = text_y(&look.hint, "(h-text_h)/2");
+            hint.push_str(&format!(",{}{enable}", drawtext(&look.hint, &text, &y)));
+        }
+        parts.push(format!("{hint}{v_h}"));
 
-        // 5) Reveal video: trim [guess, guess+reveal] + answer overlay
-        let answer = escape_drawtext_text(clip.answer.trim());
+        // Hint audio: [guess, guess+hint]
         parts.push(format!(
-            "{v_all}trim=start={guess_s:.3}:duration={reveal_s:.3},setpts=PTS-STARTPTS,\
-drawtext=text='{answer}':x=(w-text_w)/2:y=h-(text_h*2):fontsize=48:fontcolor=white:borderw=3{v_r}"
+            "{a_hsrc}atrim=start={guess_s:.3}:duration={hint_s:.3},asetpts=PTS-STARTPTS{a_h}"
         ));
+    }
+
+    // 6) Reveal video: trim [guess+hint, guess+hint+reveal] + answer overlay
+    //    (in quiz mode, the correct option on a highlight box)
+    let answer = match choices {
+        Some(choices) => {
+            let line = escape_drawtext_text(&choices.line(choices.correct));
+            let highlight = TextLook {
+                box_color: Some(look.highlight.clone()),
+                ..look.answer.clone()
+            };
+            drawtext(&highlight, &line, &text_y(&look.answer, "h-(text_h*2)"))
+        }
+        None => answer_filters(&look.answer, &clip.answer.lines()),
+    };
+    parts.push(format!(
+        "{v_all}trim=start={reveal_from_s:.3}:duration={reveal_s:.3},setpts=PTS-STARTPTS,{answer}{v_r}"
+    ));
 
-        // Reveal audio: trim [guess, guess+reveal]
+    // Reveal audio: trim [guess+hint, guess+hint+reveal]
+    parts.push(format!(
+        "{a_rsrc}atrim=start={reveal_from_s:.3}:duration={reveal_s:.3},asetpts=PTS-STARTPTS{a_r}"
+    ));
+
+    // 7) Concat guess(+hint)+reveal into one segment per clip
+    if has_hint {
         parts.push(format!(
-            "{a_rsrc}atrim=start={guess_s:.3}:duration={reveal_s:.3},asetpts=PTS-STARTPTS{a_r}"
+            "{v_g}{a_g}{v_h}{a_h}{v_r}{a_r}concat=n=3:v=1:a=1{v_i}{a_i}"
         ));
-
-        // 6) Concat guess+reveal into one segment per clip
+    } else {
         parts.push(format!("{v_g}{a_g}{v_r}{a_r}concat=n=2:v=1:a=1{v_i}{a_i}"));
     }
 
-    // Final concat
-    let mut concat_in = String::new();
-    let n: usize;
+    parts
+}
 
-    if has_intro {
-        concat_in.push_str("[vintro][aintro]");
-        n = 1 + p.clips.len();
-    } else {
-        n = p.clips.len();
+/// Drawtext filters of the answer lines.
+///
+/// The first line (artist, or the whole text answer) uses the theme size,
+/// the following ones (title, year, note) are smaller. Lines are stacked at
+/// the bottom of the frame, or from the theme `y` downwards.
+fn answer_filters(look: &TextLook, lines: &[String]) -> String {
+    if let [line] = lines {
+        return drawtext(
+            look,
+            &escape_drawtext_text(line),
+            &text_y(look, "h-(text_h*2)"),
+        );
     }
 
-    for i in 0..p.clips.len() {
-        concat_in.push_str(&format!("[v{i}][a{i}]"));
+    let styled = |k: usize| TextLook {
+        size: if k == 0 { look.size } else { look.size * 3 / 4 },
+        ..look.clone()
+    };
+    // 1.5 line height per line
+    let heights: Vec<u32> = (0..lines.len()).map(|k| styled(k).size * 3 / 2).collect();
+
+    let mut filters: Vec<String> = Vec::new();
+    for (k, line) in lines.iter().enumerate() {
+        let y = match look.y {
+            Some(y) => {
+                let below: u32 = heights[..k].iter().sum();
+                format!("h*{y}/100-text_h/2+{below}")
+            }
+            // Bottom margin, then the lines from the last one up
+            None => format!("h-{}", 48 + heights[k..].iter().sum::<u32>()),
+        };
+        filters.push(drawtext(&styled(k), &escape_drawtext_text(line), &y));
     }
+    filters.join(",")
+}
 
-    parts.push(format!("{concat_in}concat=n={n}:v=1:a=1[vout][aout]"));
+/// `drawtext` filter horizontally centered at `y`; `text` must already be
+/// escaped (see [`escape_drawtext_text`]).
+fn drawtext(look: &TextLook, text: &str, y: &str) -> String {
+    let mut filter = String::from("drawtext=");
+    if let Some(font) = &look.font {
+        filter.push_str(&format!("fontfile='{}':", escape_drawtext_text(font)));
+    }
+    filter.push_str(&format!(
+        "text='{text}':x=(w-text_w)/2:y={y}:fontsize={}:fontcolor={}:borderw={}",
+        look.size, look.color, look.border_width
+    ));
+    if let Some(color) = &look.border_color {
+        filter.push_str(&format!(":bordercolor={color}"));
+    }
+    if let Some(color) = &look.box_color {
+        filter.push_str(&format!(":box=1:boxcolor={color}:boxborderw=12"));
+    }
+    filter
+}
 
-    Ok(parts.join(";"))
+/// Vertical position of a text: the theme `y` (centre, in percent of the height), or `default`.
+fn text_y(look: &TextLook, default: &str) -> String {
+    match look.y {
+        Some(y) => format!("h*{y}/100-text_h/2"),
+        None => default.to_string(),
+    }
+}
+
+/// Title card duration of a round, in milliseconds.
+pub(crate) fn round_title_ms(round: &Round, fps: u32) -> u64 {
+    round
+        .duration
+        .map_or(DEFAULT_ROUND_TITLE_MS, |d| d.as_ms(fps))
+}
+
+/// Title card duration of a round, in seconds.
+pub(crate) fn round_title_seconds(round: &Round, fps: u32) -> f64 {
+    ms_to_seconds_f64(round_title_ms(round, fps))
+}
+
+/// Clip input, seeked on the input side: `-ss start -t total -i video`.
+pub(crate) fn push_clip_input(args: &mut Vec<String>, clip: &Clip, timings: ClipTimings, fps: u32) {
+    let start_ms = clip.start.as_ms(fps);
+
+    args.push("-ss".into());
+    args.push(format!("{:.3}", ms_to_seconds_f64(start_ms)));
+    args.push("-t".into());
+    args.push(format!("{:.3}", ms_to_seconds_f64(timings.total_ms())));
+    args.push("-i".into());
+    args.push(clip.video.trim().to_string());
+}
+
+/// Still image input, a single frame (repeated by the `loop` filter).
+pub(crate) fn push_image(args: &mut Vec<String>, path: &str) {
+    args.push("-i".into());
+    args.push(path.trim().to_string());
+}
+
+/// Still image input, looped so it can be trimmed to any duration.
+pub(crate) fn push_looped_image(args: &mut Vec<String>, path: &str) {
+    args.push("-loop".into());
+    args.push("1".into());
+    args.push("-i".into());
+    args.push(path.trim().to_string());
+}
+
+/// Encoding (simple V1)
+pub(crate) fn push_encoding_args(args: &mut Vec<String>) {
+    args.push("-c:v".into());
+    args.push("libx264".into());
+    args.push("-pix_fmt".into());
+    args.push("yuv420p".into());
+    args.push("-c:a".into());
+    args.push("aac".into());
 }
 
 /// Escape user text for ffmpeg drawtext inside single quotes.
@@ -247,58 +738,28 @@ fn parse_resolution(res: &str) -> Result<(u32, u32)> {
     Ok((w, h))
 }
 
-fn ms_to_seconds_f64(ms: u64) -> f64 {
+pub(crate) fn ms_to_seconds_f64(ms: u64) -> f64 {
     (ms as f64) / 1000.0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{Clip, Intro, Output, Project, Timings};
+    use crate::fixtures::{clip, intro, outro, round, sample_project, tc};
+    use crate::model::{
+        Answer, AnswerDetails, Clip, Project, Quiz, Round, TextStyle, Theme, ThemeRef,
+    };
 
     fn project_one_clip_no_intro() -> Project {
-        Project {
-            intro: None,
-            output: Output {
-                path: "render/out.mp4".into(),
-                resolution: Some("1280x720".into()),
-                fps: Some(30),
-            },
-            timings: Timings {
-                guess_duration: "00:00:10.000".into(),
-                reveal_duration: "00:00:05.000".into(),
-            },
-            clips: vec![Clip {
-                video: "videos/a.mp4".into(),
-                start: "00:00:01.000".into(),
-                answer: "Guns N' Roses - Live".into(),
-            }],
-        }
+        let mut p = sample_project();
+        p.clips[0].answer = "Guns N' Roses - Live".into();
+        p
     }
 
     fn project_one_clip_with_intro() -> Project {
-        Project {
-            intro: Some(Intro {
-                background: "assets/intro.png".into(),
-                title: "Blind Test Soirée".into(),
-                music: "assets/intro.mp3".into(),
-                duration: "00:00:03.000".into(),
-            }),
-            output: Output {
-                path: "render/out.mp4".into(),
-                resolution: Some("1280x720".into()),
-                fps: Some(30),
-            },
-            timings: Timings {
-                guess_duration: "00:00:10.000".into(),
-                reveal_duration: "00:00:05.000".into(),
-            },
-            clips: vec![Clip {
-                video: "videos/a.mp4".into(),
-                start: "00:00:01.000".into(),
-                answer: "Guns N' Roses - Live".into(),
-            }],
-        }
+        let mut p = project_one_clip_no_intro();
+        p.intro = Some(intro("Blind Test Soirée", "00:00:03.000"));
+        p
     }
 
     #[test]
@@ -374,13 +835,99 @@ mod tests {
     #[test]
     fn builds_concat_for_two_clips_no_intro() {
         let mut p = project_one_clip_no_intro();
+        p.clips.push(clip(
+            "videos/b.mp4",
+            "00:00:02.000",
+            "Daft Punk - One More Time",
+        ));
+
+        let spec = build_ffmpeg_command(&p).unwrap();
+        let fc = spec
+            .args
+            .iter()
+            .skip_while(|a| *a != "-filter_complex")
+            .nth(1)
+            .unwrap()
+            .clone();
+
+        // Should contain both clip labels and concat n=2
+        assert!(
+            fc.contains("[v0][a0][v1][a1]concat=n=2:v=1:a=1[vout][aout]"),
+            "filter_complex was:\n{fc}"
+        );
+    }
+
+    #[test]
+    fn draws_quiz_options_and_highlights_answer() {
+        let mut p = project_one_clip_no_intro();
+        p.quiz = Some(Quiz { seed: 1 });
+        p.clips[0].distractors = vec!["Queen".into(), "ABBA".into(), "Blondie".into()];
+
+        let spec = build_ffmpeg_command(&p).unwrap();
+        let fc = spec
+            .args
+            .iter()
+            .skip_while(|a| *a != "-filter_complex")
+            .nth(1)
+            .unwrap()
+            .clone();
+
+        let choices = crate::quiz::quiz_choices(&p).unwrap().remove(0).unwrap();
+        for o in 0..4 {
+            let line = escape_drawtext_text(&choices.line(o));
+            assert!(
+                fc.contains(&format!("text='{line}'")),
+                "filter_complex was:\n{fc}"
+            );
+        }
+        assert!(fc.contains("y=h/6-text_h/2"), "filter_complex was:\n{fc}");
+
+        let answer = escape_drawtext_text(&choices.line(choices.correct));
+        assert!(
+            fc.contains(&format!(
+                "text='{answer}':x=(w-text_w)/2:y=h-(text_h*2):fontsize=48:fontcolor=white:borderw=3\
+:box=1:boxcolor=green@0.8:boxborderw=12[v0r]"
+            )),
+            "filter_complex was:\n{fc}"
+        );
+    }
+
+    #[test]
+    fn applies_theme_to_every_screen() {
+        let mut p = project_one_clip_no_intro();
+        p.theme = Some(ThemeRef::Inline(Box::new(Theme {
+            extends: Some("neon".into()),
+            font: Some("fonts/Bangers.ttf".into()),
+            guess_image: Some("assets/guess.png".into()),
+            answer: Some(TextStyle {
+                size: Some(60),
+                box_color: Some("black@0.6".into()),
+                y: Some(80),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })));
+
+        // Second clip with a hint: three screens share the image
         p.clips.push(Clip {
             video: "videos/b.mp4".into(),
-            start: "00:00:02.000".into(),
-            answer: "Daft Punk - One More Time".into(),
+            hint: Some("Rock".into()),
+            hint_duration: Some(tc("3s")),
+            ..p.clips[0].clone()
         });
 
         let spec = build_ffmpeg_command(&p).unwrap();
+        // Image decoded once, after the two clips
+        let images: Vec<_> = spec
+            .args
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| *a == "assets/guess.png")
+            .collect();
+        assert_eq!(images.len(), 1, "args were: {:?}", spec.args);
+        assert_eq!(spec.args[images[0].0 - 1], "-i");
+        assert_ne!(spec.args[images[0].0 - 2], "1", "image must not be looped");
+        assert_eq!(spec.args.iter().filter(|a| *a == "-i").count(), 3);
         let fc = spec
             .args
             .iter()
@@ -389,10 +936,364 @@ mod tests {
             .unwrap()
             .clone();
 
-        // Should contain both clip labels and concat n=2
         assert!(
-            fc.contains("[v0][a0][v1][a1]concat=n=2:v=1:a=1[vout][aout]"),
+            fc.contains("[2:v]scale=1280:720,setsar=1,split=3[v0gimg][v1gimg][v1himg]"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains(
+                "[v0gimg]loop=loop=-1:size=1,setpts=N/(30*TB),fps=30,trim=duration=10.000,\
+drawtext=fontfile='fonts/Bangers.ttf':text='%{eif"
+            ),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("[v1himg]loop=loop=-1:size=1,setpts=N/(30*TB),fps=30,trim=duration=3.000,"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(!fc.contains("color=c="), "filter_complex was:\n{fc}");
+        assert!(
+            fc.contains(":fontsize=96:fontcolor=#ff2a6d:borderw=2:bordercolor=#05d9e8[v0g]"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains(
+                "drawtext=fontfile='fonts/Bangers.ttf':text='Guns N\\' Roses - Live':x=(w-text_w)/2\
+:y=h*80/100-text_h/2:fontsize=60:fontcolor=#05d9e8:borderw=2:bordercolor=#ff2a6d\
+:box=1:boxcolor=black@0.6:boxborderw=12[v0r]"
+            ),
+            "filter_complex was:\n{fc}"
+        );
+    }
+
+    #[test]
+    fn stacks_structured_answer_lines() {
+        let mut p = project_one_clip_no_intro();
+        p.clips[0].answer = Answer::Details(AnswerDetails {
+            artist: Some("Guns N' Roses".into()),
+            title: Some("Paradise City".into()),
+            year: Some(1987),
+            ..Default::default()
+        });
+
+        let spec = build_ffmpeg_command(&p).unwrap();
+        let fc = spec
+            .args
+            .iter()
+            .skip_while(|a| *a != "-filter_complex")
+            .nth(1)
+            .unwrap()
+            .clone();
+
+        assert!(
+            fc.contains(
+                "drawtext=text='Guns N\\' Roses':x=(w-text_w)/2:y=h-228:fontsize=48:fontcolor=white:borderw=3,\
+drawtext=text='Paradise City':x=(w-text_w)/2:y=h-156:fontsize=36:fontcolor=white:borderw=3,\
+drawtext=text='1987':x=(w-text_w)/2:y=h-102:fontsize=36:fontcolor=white:borderw=3[v0r]"
+            ),
             "filter_complex was:\n{fc}"
         );
     }
+
+    #[test]
+    fn applies_per_clip_timing_overrides() {
+        let mut p = project_one_clip_no_intro();
+        p.clips.push(Clip {
+            guess_duration: Some(tc("00:00:20.000")),
+            reveal_duration: Some(tc("00:00:08.000")),
+            ..clip("videos/b.mp4", "00:00:02.000", "Daft Punk - One More Time")
+        });
+
+        let spec = build_ffmpeg_command(&p).unwrap();
+        let fc = spec
+            .args
+            .iter()
+            .skip_while(|a| *a != "-filter_complex")
+            .nth(1)
+            .unwrap()
+            .clone();
+
+        // Clip 0 keeps the project defaults (10s + 5s)
+        let joined = spec.args.join(" ");
+        assert!(
+            joined.contains("-ss 1.000 -t 15.000 -i videos/a.mp4"),
+            "args were:\n{joined}"
+        );
+        // Clip 1 uses its own durations (20s + 8s)
+        assert!(
+            joined.contains("-ss 2.000 -t 28.000 -i videos/b.mp4"),
+            "args were:\n{joined}"
+        );
+        assert!(fc.contains("ceil(20.000-t)"), "filter_complex was:\n{fc}");
+        assert!(
+            fc.contains("[v1all]trim=start=20.000:duration=8.000"),
+            "filter_complex was:\n{fc}"
+        );
+    }
+
+    #[test]
+    fn resolves_reveal_from_clip_end() {
+        let mut p = project_one_clip_no_intro();
+        p.clips[0].end = Some(tc("00:00:31.000"));
+
+        let t = resolve_clip_timings(&p.clips[0], &p.timings, p.fps()).unwrap();
+        assert_eq!(t.guess_ms, 10_000);
+        assert_eq!(t.reveal_ms, 20_000);
+        assert_eq!(t.total_ms(), 30_000);
+    }
+
+    #[test]
+    fn inserts_hint_phase_before_reveal() {
+        let mut p = project_one_clip_no_intro();
+        p.timings.hint_duration = Some(tc("00:00:04.000"));
+        p.clips[0].end = Some(tc("00:00:31.000"));
+
+        let t = resolve_clip_timings(&p.clips[0], &p.timings, p.fps()).unwrap();
+        assert_eq!(
+            (t.guess_ms, t.hint_ms, t.reveal_ms),
+            (10_000, 4_000, 16_000)
+        );
+
+        let spec = build_ffmpeg_command(&p).unwrap();
+        let fc = spec
+            .args
+            .iter()
+            .skip_while(|a| *a != "-filter_complex")
+            .nth(1)
+            .unwrap()
+            .clone();
+
+        // "Guns N' Roses - Live": 14 letters, 7 revealed, 8 windows of 0.5 s
+        assert!(
+            fc.contains(
+                "color=c=black:s=1280x720:r=30:d=4.000,\
+drawtext=text='_ _ _ _ / _ \\' / _ _ _ _ _ / - / _ _ _ _'\
+:x=(w-text_w)/2:y=(h-text_h)/2:fontsize=72:fontcolor=white:borderw=4\
+:enable='gte(t,0.000)*lt(t,0.500)',"
+            ),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("[a0hsrc]atrim=start=10.000:duration=4.000,asetpts=PTS-STARTPTS[a0h]"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("[v0all]trim=start=14.000:duration=16.000"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("[v0g][a0g][v0h][a0h][v0r][a0r]concat=n=3:v=1:a=1[v0][a0]"),
+            "filter_complex was:\n{fc}"
+        );
+    }
+
+    #[test]
+    fn rejects_clip_end_inside_guess_phase() {
+        let mut p = project_one_clip_no_intro();
+        p.clips[0].end = Some(tc("00:00:05.000"));
+        assert!(build_ffmpeg_command(&p).is_err());
+
+        // Round clips are reported with their path in the project file
+        let mut p = project_one_clip_no_intro();
+        let mut late = p.clips[0].clone();
+        late.end = Some(tc("00:00:05.000"));
+        p.rounds = vec![round("Bonus", vec![p.clips[0].clone(), late])];
+        let err = build_ffmpeg_command(&p).unwrap_err();
+        assert_eq!(err.to_string(), "rounds[0].clips[1] has invalid timings");
+        let err = crate::timeline::timeline(&p).unwrap_err();
+        assert_eq!(err.to_string(), "rounds[0].clips[1] has invalid timings");
+    }
+
+    #[test]
+    fn builds_title_cards_for_rounds() {
+        let mut p = project_one_clip_no_intro();
+        p.rounds = vec![
+            Round {
+                background: Some("assets/80s.png".into()),
+                ..round(
+                    "Années 80",
+                    vec![clip("videos/b.mp4", "00:00:02.000", "A-ha - Take On Me")],
+                )
+            },
+            Round {
+                duration: Some(tc("00:00:04.000")),
+                ..round(
+                    "Films",
+                    vec![clip("videos/c.mp4", "00:00:03.000", "Star Wars")],
+                )
+            },
+        ];
+
+        let spec = build_ffmpeg_command(&p).unwrap();
+
+        // clip inputs first (0..=2), then the round background (3)
+        let joined = spec.args.join(" ");
+        assert!(
+            joined.contains("-i videos/c.mp4 -loop 1 -i assets/80s.png"),
+            "args were:\n{joined}"
+        );
+
+        let fc = spec
+            .args
+            .iter()
+            .skip_while(|a| *a != "-filter_complex")
+            .nth(1)
+            .unwrap()
+            .clone();
+
+        assert!(
+            fc.contains("[3:v]scale=1280:720,fps=30,setsar=1,trim=duration=3.000"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(fc.contains("drawtext=text='Années 80'"));
+        assert!(
+            fc.contains("color=c=black:s=1280x720:r=30:d=4.000"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(fc.contains("[around1]"));
+
+        assert!(
+            fc.contains(
+                "[v0][a0][vround0][around0][v1][a1][vround1][around1][v2][a2]concat=n=5:v=1:a=1[vout][aout]"
+            ),
+            "filter_complex was:\n{fc}"
+        );
+    }
+
+    #[test]
+    fn appends_outro_after_last_clip() {
+        let mut p = project_one_clip_with_intro();
+        p.outro = Some(outro("Merci d'avoir joué", "00:00:04.000"));
+
+        let spec = build_ffmpeg_command(&p).unwrap();
+
+        // intro (0,1), clip (2), outro (3,4)
+        let joined = spec.args.join(" ");
+        assert!(
+            joined.contains("-i videos/a.mp4 -loop 1 -i assets/outro.png -i assets/outro.mp3"),
+            "args were:\n{joined}"
+        );
+
+        let fc = spec
+            .args
+            .iter()
+            .skip_while(|a| *a != "-filter_complex")
+            .nth(1)
+            .unwrap()
+            .clone();
+
+        assert!(
+            fc.contains("[3:v]scale=1280:720"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("Merci d\\'avoir joué"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("[4:a]atrim=0:4.000,asetpts=PTS-STARTPTS[aoutro]"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("[vintro][aintro][v0][a0][voutro][aoutro]concat=n=3:v=1:a=1[vout][aout]"),
+            "filter_complex was:\n{fc}"
+        );
+    }
+
+    #[test]
+    fn seeks_clips_with_input_options() {
+        let mut p = project_one_clip_with_intro();
+        p.clips.push(clip(
+            "videos/b.mp4",
+            "00:03:00.500",
+            "Daft Punk - One More Time",
+        ));
+
+        let spec = build_ffmpeg_command(&p).unwrap();
+
+        // `-ss`/`-t` come right before each clip `-i`, never before intro inputs
+        let expected: Vec<String> = [
+            "-y",
+            "-loop",
+            "1",
+            "-i",
+            "assets/intro.png",
+            "-i",
+            "assets/intro.mp3",
+            "-ss",
+            "1.000",
+            "-t",
+            "15.000",
+            "-i",
+            "videos/a.mp4",
+            "-ss",
+            "180.500",
+            "-t",
+            "15.000",
+            "-i",
+            "videos/b.mp4",
+            "-filter_complex",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(spec.args[..expected.len()], expected[..]);
+
+        let fc = spec.args[expected.len()].clone();
+
+        // No more decoding from 00:00: the graph only resets timestamps
+        assert!(
+            !fc.contains("trim=start=1.000"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("[3:v]setpts=PTS-STARTPTS,scale=1280:720,fps=30,setsar=1[v1all]"),
+            "filter_complex was:\n{fc}"
+        );
+        assert!(
+            fc.contains("[3:a]asetpts=PTS-STARTPTS[a1all]"),
+            "filter_complex was:\n{fc}"
+        );
+
+        // Reveal still starts after the guess phase, relative to the seeked input
+        assert!(
+            fc.contains("[v1all]trim=start=10.000:duration=5.000"),
+            "filter_complex was:\n{fc}"
+        );
+    }
+
+    #[test]
+    fn computes_expected_duration() {
+        let mut p = project_one_clip_with_intro();
+        // intro 3s + clip 15s
+        assert_eq!(expected_duration_ms(&p).unwrap(), 18_000);
+
+        p.clips[0].guess_duration = Some(tc("00:00:20.000"));
+        p.rounds.push(round(
+            "Films",
+            vec![clip("videos/b.mp4", "00:00:02.000", "Star Wars")],
+        ));
+        p.outro = Some(outro("Merci", "00:00:04.000"));
+        // intro 3s + clip 25s + round card 3s + clip 15s + outro 4s
+        assert_eq!(expected_duration_ms(&p).unwrap(), 50_000);
+    }
+
+    #[test]
+    fn embeds_chapters_as_last_input() {
+        let mut spec = build_ffmpeg_command(&project_one_clip_with_intro()).unwrap();
+        let inputs = spec.args.iter().filter(|a| *a == "-i").count();
+        embed_chapters(&mut spec, "render/out.ffmetadata");
+
+        let joined = spec.args.join(" ");
+        assert!(
+            joined.contains(" -f ffmetadata -i render/out.ffmetadata -filter_complex "),
+            "{joined}"
+        );
+        assert!(
+            joined.ends_with(&format!(
+                " -map_metadata {inputs} -map_chapters {inputs} render/out.mp4"
+            )),
+            "{joined}"
+        );
+    }
 }
@@ -0,0 +1,75 @@
+// src/fixtures.rs
+//
+// Projects shared by the unit tests: start from `sample_project()` and only
+// set what a test is about, so a new model field is added here once.
+
+use crate::model::{CURRENT_VERSION, Clip, Intro, Output, Outro, Project, Round, Timings};
+use crate::timecode::Timecode;
+
+pub(crate) fn tc(s: &str) -> Timecode {
+    s.parse().unwrap()
+}
+
+/// One clip (`videos/a.mp4` from 1s, "Artist - Track"), 1280x720 at 30 fps,
+/// 10s guess + 5s reveal, no intro, rounds, outro, quiz or theme.
+pub(crate) fn sample_project() -> Project {
+    Project {
+        schema: None,
+        version: CURRENT_VERSION,
+        intro: None,
+        output: Output {
+            path: "render/out.mp4".into(),
+            resolution: Some("1280x720".into()),
+            fps: Some(30),
+        },
+        timings: Timings {
+            guess_duration: tc("00:00:10.000"),
+            reveal_duration: tc("00:00:05.000"),
+            hint_duration: None,
+        },
+        clips: vec![clip("videos/a.mp4", "00:00:01.000", "Artist - Track")],
+        rounds: Vec::new(),
+        outro: None,
+        quiz: None,
+        theme: None,
+    }
+}
+
+pub(crate) fn clip(video: &str, start: &str, answer: &str) -> Clip {
+    Clip {
+        video: video.into(),
+        start: tc(start),
+        answer: answer.into(),
+        ..Default::default()
+    }
+}
+
+/// Intro on `assets/intro.png` with `assets/intro.mp3`.
+pub(crate) fn intro(title: &str, duration: &str) -> Intro {
+    Intro {
+        background: "assets/intro.png".into(),
+        title: title.into(),
+        music: "assets/intro.mp3".into(),
+        duration: tc(duration),
+    }
+}
+
+/// Outro on `assets/outro.png` with `assets/outro.mp3`.
+pub(crate) fn outro(text: &str, duration: &str) -> Outro {
+    Outro {
+        background: "assets/outro.png".into(),
+        text: text.into(),
+        music: "assets/outro.mp3".into(),
+        duration: tc(duration),
+    }
+}
+
+/// Round without background, shown for the default title duration.
+pub(crate) fn round(title: &str, clips: Vec<Clip>) -> Round {
+    Round {
+        title: title.into(),
+        background: None,
+        duration: None,
+        clips,
+    }
+}
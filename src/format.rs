@@ -0,0 +1,75 @@
+// src/format.rs
+
+use anyhow::Result;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::path::Path;
+
+/// Syntax of a project file, chosen from its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ProjectFormat {
+    /// `.yaml`/`.yml` and `.toml` files; anything else is read as JSON.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let ext = path
+            .as_ref()
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase());
+
+        match ext.as_deref() {
+            Some("yaml" | "yml") => ProjectFormat::Yaml,
+            Some("toml") => ProjectFormat::Toml,
+            _ => ProjectFormat::Json,
+        }
+    }
+
+    /// Name used in messages ("invalid YAML in file: ...").
+    pub fn name(self) -> &'static str {
+        match self {
+            ProjectFormat::Json => "JSON",
+            ProjectFormat::Yaml => "YAML",
+            ProjectFormat::Toml => "TOML",
+        }
+    }
+
+    /// Deserialize `source`; the parser's own error stays in the chain
+    /// (see [`crate::diagnostic::load_error_issue`]).
+    pub fn parse<T: DeserializeOwned>(self, source: &str) -> Result<T> {
+        Ok(match self {
+            ProjectFormat::Json => serde_json::from_str(source)?,
+            ProjectFormat::Yaml => serde_yaml::from_str(source)?,
+            ProjectFormat::Toml => toml::from_str(source)?,
+        })
+    }
+
+    /// Human-readable text of `value` in this format.
+    pub fn to_string_pretty<T: Serialize>(self, value: &T) -> Result<String> {
+        Ok(match self {
+            ProjectFormat::Json => serde_json::to_string_pretty(value)?,
+            ProjectFormat::Yaml => serde_yaml::to_string(value)?,
+            ProjectFormat::Toml => toml::to_string_pretty(value)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_format_from_extension() {
+        assert_eq!(ProjectFormat::from_path("a.json"), ProjectFormat::Json);
+        assert_eq!(
+            ProjectFormat::from_path("shows/a.yaml"),
+            ProjectFormat::Yaml
+        );
+        assert_eq!(ProjectFormat::from_path("a.YML"), ProjectFormat::Yaml);
+        assert_eq!(ProjectFormat::from_path("a.toml"), ProjectFormat::Toml);
+        assert_eq!(ProjectFormat::from_path("montage"), ProjectFormat::Json);
+    }
+}
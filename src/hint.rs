@@ -0,0 +1,149 @@
+// src/hint.rs
+
+use sha2::{Digest, Sha256};
+
+use crate::draw::{seeded_rng, shuffle};
+use crate::model::Clip;
+
+/// Most hangman steps drawn during a hint phase (several letters per step beyond that).
+pub const HANGMAN_MAX_STEPS: usize = 10;
+
+/// Text shown during part of a hint phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HintStep {
+    /// Start, relative to the start of the hint phase
+    pub start_ms: u64,
+    /// End, relative to the start of the hint phase
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// What the hint phase of `clip` shows, over `hint_ms`.
+///
+/// A clip `hint` is shown for the whole phase. Otherwise the answer is shown
+/// as underscores (`_ _ _ _ / _ _ _`), and half of its letters are revealed
+/// one step at a time. The letter order only depends on the answer, so renders
+/// are reproducible.
+pub fn hint_steps(clip: &Clip, hint_ms: u64) -> Vec<HintStep> {
+    if let Some(hint) = clip.hint.as_deref() {
+        return vec![HintStep {
+            start_ms: 0,
+            end_ms: hint_ms,
+            text: hint.trim().to_string(),
+        }];
+    }
+
+    let answer = clip.answer.summary();
+    let answer_seed = answer_seed(&answer);
+    let answer: Vec<char> = answer.chars().collect();
+    let mut hidden: Vec<usize> = (0..answer.len())
+        .filter(|&i| answer[i].is_alphanumeric())
+        .collect();
+    shuffle(&mut hidden, &mut seeded_rng(answer_seed));
+
+    let to_reveal = hidden.len() / 2;
+    let steps = to_reveal.min(HANGMAN_MAX_STEPS);
+    let mut revealed = vec![false; answer.len()];
+    (0..=steps)
+        .map(|k| {
+            let count = (to_reveal * k).checked_div(steps).unwrap_or(0);
+            for &i in &hidden[..count] {
+                revealed[i] = true;
+            }
+            HintStep {
+                start_ms: hint_ms * k as u64 / (steps as u64 + 1),
+                end_ms: hint_ms * (k as u64 + 1) / (steps as u64 + 1),
+                text: hangman(&answer, &revealed),
+            }
+        })
+        .collect()
+}
+
+/// Answer with hidden letters as `_`, one space between characters and `/` between words.
+fn hangman(answer: &[char], revealed: &[bool]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    for (i, c) in answer.iter().enumerate() {
+        if c.is_whitespace() {
+            // One separator per gap, however many spaces it has
+            if out.last().is_some_and(|l| l != "/") {
+                out.push("/".into());
+            }
+        } else if c.is_alphanumeric() && !revealed[i] {
+            out.push("_".into());
+        } else {
+            out.push(c.to_string());
+        }
+    }
+    out.join(" ")
+}
+
+fn answer_seed(answer: &str) -> u64 {
+    let digest = Sha256::digest(answer.trim().as_bytes());
+    u64::from_le_bytes(digest[..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip(answer: &str) -> Clip {
+        Clip {
+            answer: answer.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn masks_the_answer_word_by_word() {
+        let steps = hint_steps(&clip("Guns N' Roses"), 6_000);
+        assert_eq!(steps[0].text, "_ _ _ _ / _ ' / _ _ _ _ _");
+        assert_eq!((steps[0].start_ms, steps[0].end_ms), (0, 1_000));
+        assert_eq!(steps.last().unwrap().end_ms, 6_000);
+
+        // 10 letters: 5 revealed, one per step
+        assert_eq!(steps.len(), 6);
+        for (k, step) in steps.iter().enumerate() {
+            assert_eq!(step.text.matches('_').count(), 10 - k, "{step:?}");
+        }
+    }
+
+    #[test]
+    fn reveals_letters_in_a_stable_order() {
+        let a = hint_steps(&clip("Daft Punk"), 5_000);
+        assert_eq!(a, hint_steps(&clip("Daft Punk"), 5_000));
+        // Pinned order: the same answer must give the same hints on every build
+        let texts: Vec<&str> = a.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "_ _ _ _ / _ _ _ _",
+                "_ _ _ _ / _ u _ _",
+                "_ _ f _ / _ u _ _",
+                "_ _ f t / _ u _ _",
+                "_ _ f t / _ u _ k",
+            ]
+        );
+
+        // Revealed letters stay revealed
+        for w in a.windows(2) {
+            for (before, after) in w[0].text.chars().zip(w[1].text.chars()) {
+                assert!(before == '_' || before == after, "{w:?}");
+            }
+        }
+        assert_eq!(hangman(&['a', ' ', ' ', 'b'], &[true; 4]), "a / b");
+    }
+
+    #[test]
+    fn shows_the_category_hint_as_is() {
+        let mut c = clip("Queen");
+        c.hint = Some(" 1975 · Rock ".into());
+        assert_eq!(
+            hint_steps(&c, 4_000),
+            [HintStep {
+                start_ms: 0,
+                end_ms: 4_000,
+                text: "1975 · Rock".into()
+            }]
+        );
+    }
+}
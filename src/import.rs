@@ -0,0 +1,472 @@
+// src/import.rs
+
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+use crate::model::{Clip, DEFAULT_FPS, Project, Round};
+use crate::timecode::Timecode;
+use crate::wizard::{answer_from_file_name, default_project};
+
+/// Header of the CSV column read for each clip field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvColumns {
+    pub video: String,
+    pub start: String,
+    pub answer: String,
+    /// Optional: without this column every clip goes to the top-level `clips`
+    pub round: String,
+}
+
+impl Default for CsvColumns {
+    fn default() -> Self {
+        CsvColumns {
+            video: "video".into(),
+            start: "start".into(),
+            answer: "answer".into(),
+            round: "round".into(),
+        }
+    }
+}
+
+impl CsvColumns {
+    /// Apply a `field=header` mapping, e.g. `answer=Titre`.
+    pub fn set(&mut self, mapping: &str) -> Result<()> {
+        let Some((field, header)) = mapping.split_once('=') else {
+            bail!("invalid column mapping '{mapping}' (expected FIELD=HEADER, e.g. answer=Titre)");
+        };
+        let header = header.trim().to_string();
+        match field.trim() {
+            "video" => self.video = header,
+            "start" => self.start = header,
+            "answer" => self.answer = header,
+            "round" => self.round = header,
+            other => bail!("unknown clip field '{other}' (expected video, start, answer or round)"),
+        }
+        Ok(())
+    }
+}
+
+/// Guess the delimiter of a spreadsheet export from its header line:
+/// `;` (French Excel), tab, or `,` by default.
+pub fn sniff_delimiter(source: &str) -> u8 {
+    let header = source.lines().next().unwrap_or("");
+    [b';', b'\t', b',']
+        .into_iter()
+        .max_by_key(|&d| (header.matches(d as char).count(), d == b','))
+        .unwrap_or(b',')
+}
+
+/// Build a project (with the quick mode defaults) from a CSV list of clips.
+///
+/// Clips with a `round` value are grouped into rounds, in order of first
+/// appearance; the others go to the top-level `clips`. Every bad row is
+/// reported, with its line number in the file.
+pub fn import_csv<R: Read>(reader: R, columns: &CsvColumns, delimiter: u8) -> Result<Project> {
+    let mut csv = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let headers = csv
+        .headers()
+        .context("failed to read the CSV header")?
+        .clone();
+    let find = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.trim_start_matches('\u{feff}').eq_ignore_ascii_case(name))
+    };
+    let required = |name: &str| {
+        find(name).with_context(|| {
+            let found: Vec<&str> = headers.iter().collect();
+            format!("missing column '{name}' (found: {})", found.join(", "))
+        })
+    };
+    let video_col = required(&columns.video)?;
+    let start_col = required(&columns.start)?;
+    let answer_col = required(&columns.answer)?;
+    let round_col = find(&columns.round);
+
+    let mut clips = Vec::new();
+    let mut rounds: Vec<Round> = Vec::new();
+    let mut errors = Vec::new();
+
+    for record in csv.records() {
+        let record = record.context("failed to read CSV")?;
+        let line = record.position().map(|p| p.line()).unwrap_or(0);
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let field = |i: usize| record.get(i).unwrap_or("");
+
+        let mut row_errors = Vec::new();
+        for (name, i) in [(&columns.video, video_col), (&columns.answer, answer_col)] {
+            if field(i).is_empty() {
+                row_errors.push(format!("line {line}: empty {name}"));
+            }
+        }
+        let start = field(start_col)
+            .parse::<Timecode>()
+            .and_then(|tc| tc.check_fps(DEFAULT_FPS).map(|()| tc));
+        let start = match start {
+            Ok(tc) => tc,
+            Err(e) => {
+                row_errors.push(format!("line {line}: {}: {e:#}", columns.start));
+                Timecode::ZERO
+            }
+        };
+        if !row_errors.is_empty() {
+            errors.extend(row_errors);
+            continue;
+        }
+
+        let clip = Clip {
+            video: field(video_col).to_string(),
+            start,
+            answer: field(answer_col).into(),
+            ..Default::default()
+        };
+
+        match round_col.map(field).filter(|r| !r.is_empty()) {
+            None => clips.push(clip),
+            Some(title) => match rounds.iter_mut().find(|r| r.title == title) {
+                Some(round) => round.clips.push(clip),
+                None => rounds.push(Round {
+                    title: title.to_string(),
+                    background: None,
+                    duration: None,
+                    clips: vec![clip],
+                }),
+            },
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!(
+            "{} error(s) in CSV rows:\n  {}",
+            errors.len(),
+            errors.join("\n  ")
+        );
+    }
+    if clips.is_empty() && rounds.is_empty() {
+        bail!("no clip found in the CSV file");
+    }
+
+    Ok(default_project(clips, rounds))
+}
+
+/// One playlist entry, before it becomes a clip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlaylistEntry {
+    location: String,
+    title: Option<String>,
+}
+
+/// Build a project (with the quick mode defaults) from an M3U/M3U8 or PLS playlist.
+///
+/// The answer is the entry title (`#EXTINF`, `TitleN=`) or else the file name
+/// stem, like `blindtest new --quick`. Relative entries are joined to `base`
+/// (keep it empty to write them unchanged); `file://` URLs become paths.
+pub fn import_playlist(source: &str, base: &Path) -> Result<Project> {
+    let source = source.trim_start_matches('\u{feff}');
+    let is_pls = source
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .is_some_and(|l| l.eq_ignore_ascii_case("[playlist]"));
+    let entries = if is_pls {
+        parse_pls(source)
+    } else {
+        parse_m3u(source)
+    };
+
+    let mut clips = Vec::new();
+    let mut errors = Vec::new();
+    for (i, entry) in entries.into_iter().enumerate() {
+        let Some(path) = entry_path(&entry.location) else {
+            errors.push(format!(
+                "entry {}: '{}' is not a local file",
+                i + 1,
+                entry.location
+            ));
+            continue;
+        };
+        let video = if path.is_relative() && !base.as_os_str().is_empty() {
+            base.join(&path)
+        } else {
+            path
+        };
+        let answer = entry.title.unwrap_or_else(|| answer_from_file_name(&video));
+
+        clips.push(Clip {
+            video: video.to_string_lossy().into_owned(),
+            start: Timecode::ZERO,
+            answer: answer.into(),
+            ..Default::default()
+        });
+    }
+
+    if !errors.is_empty() {
+        bail!(
+            "{} error(s) in playlist:\n  {}",
+            errors.len(),
+            errors.join("\n  ")
+        );
+    }
+    if clips.is_empty() {
+        bail!("no entry found in the playlist");
+    }
+
+    Ok(default_project(clips, Vec::new()))
+}
+
+/// `#EXTINF:<duration> [attributes],<title>` lines give the title of the next entry.
+fn parse_m3u(source: &str) -> Vec<PlaylistEntry> {
+    let mut entries = Vec::new();
+    let mut title = None;
+
+    for line in source.lines().map(str::trim) {
+        if let Some(info) = line.strip_prefix("#EXTINF:") {
+            title = extinf_title(info);
+        } else if !line.is_empty() && !line.starts_with('#') {
+            entries.push(PlaylistEntry {
+                location: line.to_string(),
+                title: title.take(),
+            });
+        }
+    }
+    entries
+}
+
+/// Title after the first comma outside quoted attribute values.
+fn extinf_title(info: &str) -> Option<String> {
+    let mut quoted = false;
+    let comma = info.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ',' if !quoted => Some(i),
+        _ => None,
+    })?;
+    let title = info[comma + 1..].trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// `FileN=` / `TitleN=` pairs, in the order of `N`.
+fn parse_pls(source: &str) -> Vec<PlaylistEntry> {
+    let mut entries: BTreeMap<u32, (Option<String>, Option<String>)> = BTreeMap::new();
+
+    for line in source.lines().map(str::trim) {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_ascii_lowercase();
+        let value = value.trim().to_string();
+        let (slot, n) = if let Some(n) = key.strip_prefix("file") {
+            (0, n)
+        } else if let Some(n) = key.strip_prefix("title") {
+            (1, n)
+        } else {
+            continue;
+        };
+        let Ok(n) = n.parse() else {
+            continue;
+        };
+
+        let entry = entries.entry(n).or_default();
+        if slot == 0 {
+            entry.0 = Some(value);
+        } else {
+            entry.1 = Some(value).filter(|t| !t.is_empty());
+        }
+    }
+
+    entries
+        .into_values()
+        .filter_map(|(file, title)| {
+            Some(PlaylistEntry {
+                location: file.filter(|f| !f.is_empty())?,
+                title,
+            })
+        })
+        .collect()
+}
+
+/// Local path of an entry: a plain path or a `file://` URL (other URLs are rejected).
+fn entry_path(location: &str) -> Option<std::path::PathBuf> {
+    if let Some(url) = location.strip_prefix("file://") {
+        // file:///abs/path or file://localhost/abs/path
+        let path = url.strip_prefix("localhost").unwrap_or(url);
+        return Some(percent_decode(path).into());
+    }
+    let is_url = location
+        .split_once("://")
+        .is_some_and(|(scheme, _)| scheme.chars().all(|c| c.is_ascii_alphanumeric()));
+    (!is_url).then(|| location.into())
+}
+
+/// Decode `%XX` escapes (invalid sequences are kept as is).
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(csv: &str) -> Result<Project> {
+        import_csv(csv.as_bytes(), &CsvColumns::default(), sniff_delimiter(csv))
+    }
+
+    #[test]
+    fn imports_clips_and_rounds() {
+        let p = import(
+            "video,start,answer,round\n\
+             a.mp4,1m30s,Queen - Bohemian Rhapsody,\n\
+             b.mp4,00:00:12.000,ABBA - SOS,Années 70\n\
+             \n\
+             c.mp4,45,\"Daft Punk, One More Time\",Années 2000\n\
+             d.mp4,1:00,Boney M - Rasputin,Années 70\n",
+        )
+        .unwrap();
+
+        assert_eq!(p.clips.len(), 1);
+        assert_eq!(p.clips[0].start, Timecode::from_secs(90));
+        let titles: Vec<_> = p.rounds.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, ["Années 70", "Années 2000"]);
+        assert_eq!(p.rounds[0].clips.len(), 2);
+        assert_eq!(
+            p.rounds[1].clips[0].answer.summary(),
+            "Daft Punk, One More Time"
+        );
+        crate::validate_project(&p).unwrap();
+    }
+
+    #[test]
+    fn maps_columns_and_sniffs_semicolons() {
+        let mut columns = CsvColumns::default();
+        columns.set("video=Fichier").unwrap();
+        columns.set("start = Début").unwrap();
+        columns.set("answer=Réponse").unwrap();
+        assert!(columns.set("artist=Artiste").is_err());
+
+        let csv = "\u{feff}Fichier;Début;Réponse\nclip.mp4;10s;Réponse A\n";
+        assert_eq!(sniff_delimiter(csv), b';');
+        let p = import_csv(csv.as_bytes(), &columns, b';').unwrap();
+        assert_eq!(p.clips[0].video, "clip.mp4");
+        assert!(p.rounds.is_empty());
+    }
+
+    #[test]
+    fn reports_every_bad_row() {
+        let err = import(
+            "video,start,answer\n\
+             a.mp4,banana,A\n\
+             b.mp4,10s,B\n\
+             ,00:00:01:45,\n",
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.starts_with("4 error(s) in CSV rows"), "{err}");
+        assert!(
+            err.contains("line 2: start: invalid timecode 'banana'"),
+            "{err}"
+        );
+        assert!(err.contains("line 4: empty video"), "{err}");
+        assert!(err.contains("line 4: empty answer"), "{err}");
+        assert!(err.contains("line 4: start: "), "{err}");
+        assert!(!err.contains("line 3"), "{err}");
+    }
+
+    #[test]
+    fn requires_mapped_columns() {
+        let err = import("file,start,answer\na.mp4,1s,A\n")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "missing column 'video' (found: file, start, answer)");
+    }
+
+    #[test]
+    fn imports_m3u_titles_and_stems() {
+        let m3u = "#EXTM3U\n\
+                   #EXTINF:215,Queen - Bohemian Rhapsody\n\
+                   music/queen.mp4\n\
+                   \n\
+                   #EXTINF:-1 tvg-name=\"a,b\",Daft Punk - One More Time\n\
+                   /abs/daft.mp4\n\
+                   # a comment\n\
+                   file:///media/My%20Clips/abba.mp4\n";
+
+        let p = import_playlist(m3u, Path::new("/shows")).unwrap();
+        let clips: Vec<_> = p
+            .clips
+            .iter()
+            .map(|c| (c.video.as_str(), c.answer.clone()))
+            .collect();
+        assert_eq!(
+            clips,
+            [
+                ("/shows/music/queen.mp4", "Queen - Bohemian Rhapsody".into()),
+                ("/abs/daft.mp4", "Daft Punk - One More Time".into()),
+                ("/media/My Clips/abba.mp4", "abba".into()),
+            ]
+        );
+        assert!(p.clips.iter().all(|c| c.start == Timecode::ZERO));
+
+        // An empty base keeps relative entries unchanged
+        let p = import_playlist(m3u, Path::new("")).unwrap();
+        assert_eq!(p.clips[0].video, "music/queen.mp4");
+    }
+
+    #[test]
+    fn imports_pls_in_entry_order() {
+        let pls = "[playlist]\n\
+                   File2=b.mp4\n\
+                   Title1=Song A\n\
+                   File1=a.mp4\n\
+                   Length1=-1\n\
+                   NumberOfEntries=2\n\
+                   Version=2\n";
+
+        let p = import_playlist(pls, Path::new("")).unwrap();
+        assert_eq!(p.clips[0].video, "a.mp4");
+        assert_eq!(p.clips[0].answer.summary(), "Song A");
+        assert_eq!(p.clips[1].answer.summary(), "b");
+    }
+
+    #[test]
+    fn rejects_remote_entries() {
+        let err = import_playlist("a.mp4\nhttp://radio.example/stream\n", Path::new(""))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("entry 2: 'http://radio.example/stream' is not a local file"),
+            "{err}"
+        );
+        assert!(import_playlist("#EXTM3U\n", Path::new("")).is_err());
+    }
+}
@@ -1,36 +1,105 @@
 // src/lib.rs
 
+pub mod answers;
+pub mod chapters;
+pub mod diagnostic;
+pub mod draw;
 pub mod ffmpeg;
 pub mod ffmpeg_command;
+pub mod format;
+pub mod hint;
+pub mod import;
+pub mod migrate;
 pub mod model;
+pub mod probe;
+pub mod quiz;
+pub mod schema;
+pub mod segments;
+pub mod theme;
 pub mod timecode;
+pub mod timeline;
 pub mod validate;
 pub mod wizard;
 
+#[cfg(test)]
+mod fixtures;
+
 pub use crate::ffmpeg_command::build_ffmpeg_command;
+pub use crate::format::ProjectFormat;
 pub use crate::validate::validate_project;
 
 use anyhow::{Context, Result};
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::model::Project;
+use crate::model::{CURRENT_VERSION, Project};
 
-/// Load a Project from a JSON file on disk.
+/// Load a Project from a JSON, YAML or TOML file on disk (see [`ProjectFormat::from_path`]).
+///
+/// Relative paths in the project are resolved against the file's directory
+/// (see [`Project::resolve_paths`]). To keep them relative to the working
+/// directory, use [`read_project_source`] and [`parse_project_as`] instead.
 pub fn load_project<P: AsRef<Path>>(path: P) -> Result<Project> {
     let path_ref = path.as_ref();
+    let buf = read_project_source(path_ref)?;
+    let format = ProjectFormat::from_path(path_ref);
+
+    let mut project = parse_project_as(&buf, format)
+        .with_context(|| format!("invalid {} in file: {}", format.name(), path_ref.display()))?;
+    project.resolve_paths(&project_dir(path_ref));
+
+    Ok(project)
+}
+
+/// Directory relative paths of a project file are resolved against
+/// (empty for a file in the working directory).
+pub fn project_dir<P: AsRef<Path>>(path: P) -> PathBuf {
+    path.as_ref()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Read the raw text of a project file, kept to point diagnostics at lines and columns.
+pub fn read_project_source<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path_ref = path.as_ref();
 
     let mut file = File::open(path_ref)
-        .with_context(|| format!("failed to open JSON file: {}", path_ref.display()))?;
+        .with_context(|| format!("failed to open project file: {}", path_ref.display()))?;
 
-    // Read the whole file to provide better error messages if JSON is invalid.
     let mut buf = String::new();
     file.read_to_string(&mut buf)
-        .with_context(|| format!("failed to read JSON file: {}", path_ref.display()))?;
+        .with_context(|| format!("failed to read project file: {}", path_ref.display()))?;
 
-    let project: Project = serde_json::from_str(&buf)
-        .with_context(|| format!("invalid JSON in file: {}", path_ref.display()))?;
+    Ok(buf)
+}
+
+/// Parse a Project from JSON text (the error keeps serde's line and column).
+pub fn parse_project(source: &str) -> Result<Project> {
+    parse_project_as(source, ProjectFormat::Json)
+}
+
+/// Parse a Project from JSON, YAML or TOML text.
+///
+/// Documents written in an older format version are upgraded on the fly
+/// (see [`migrate`]); the returned project is always at [`CURRENT_VERSION`].
+pub fn parse_project_as(source: &str, format: ProjectFormat) -> Result<Project> {
+    let version = migrate::document_version(source, format).unwrap_or(CURRENT_VERSION);
+    migrate::check_version(version)?;
+
+    let mut project: Project = if version < CURRENT_VERSION {
+        let mut doc: serde_json::Value = format.parse(source)?;
+        if migrate::upgrade(&mut doc, version)? {
+            serde_json::from_value(doc)?
+        } else {
+            // Same structure: parse the original text to keep error positions
+            format.parse(source)?
+        }
+    } else {
+        format.parse(source)?
+    };
+    project.version = CURRENT_VERSION;
 
     Ok(project)
 }
@@ -42,8 +111,7 @@ pub fn load_project_from_reader<R: Read>(mut reader: R) -> Result<Project> {
         .read_to_string(&mut buf)
         .context("failed to read JSON from reader")?;
 
-    let project: Project = serde_json::from_str(&buf).context("invalid JSON")?;
-    Ok(project)
+    parse_project(&buf).context("invalid JSON")
 }
 
 #[cfg(test)]
@@ -66,7 +134,31 @@ mod tests {
         assert_eq!(project.output.path, "render/out.mp4");
         assert_eq!(project.output.fps, Some(30));
         assert_eq!(project.clips.len(), 1);
-        assert_eq!(project.clips[0].answer, "Artist - Track");
+        assert_eq!(project.clips[0].answer.summary(), "Artist - Track");
+    }
+
+    #[test]
+    fn load_project_from_reader_parses_rounds() {
+        let json = r#"
+        {
+          "output": { "path": "render/out.mp4" },
+          "timings": { "guess_duration": "00:00:10.000", "reveal_duration": "00:00:05.000" },
+          "rounds": [
+            {
+              "title": "Années 80",
+              "clips": [
+                { "video": "videos/a.mp4", "start": "00:00:01.000", "answer": "A" }
+              ]
+            }
+          ]
+        }
+        "#;
+
+        let project = load_project_from_reader(json.as_bytes()).unwrap();
+        assert!(project.clips.is_empty());
+        assert_eq!(project.rounds.len(), 1);
+        assert_eq!(project.rounds[0].title, "Années 80");
+        assert_eq!(project.all_clips().count(), 1);
     }
 
     #[test]
@@ -81,6 +173,54 @@ mod tests {
     }
 }
 
+#[cfg(test)]
+mod path_tests {
+    use super::*;
+
+    const JSON: &str = r#"
+    {
+      "intro": { "background": "assets/intro.png", "title": "T", "music": "/abs/intro.mp3", "duration": "00:00:05.000" },
+      "output": { "path": "render/out.mp4" },
+      "timings": { "guess_duration": "00:00:10.000", "reveal_duration": "00:00:05.000" },
+      "clips": [ { "video": "videos/a.mp4", "start": "00:00:01.000", "answer": "A" } ],
+      "rounds": [
+        { "title": "R", "background": "assets/r.png",
+          "clips": [ { "video": "videos/b.mp4", "start": "00:00:01.000", "answer": "B" } ] }
+      ]
+    }
+    "#;
+
+    #[test]
+    fn load_project_resolves_paths_against_file_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let shows = dir.path().join("shows");
+        std::fs::create_dir(&shows).unwrap();
+        let file = shows.join("friday.json");
+        std::fs::write(&file, JSON).unwrap();
+
+        let project = load_project(&file).unwrap();
+        let expect = |p: &str| shows.join(p).to_string_lossy().into_owned();
+
+        assert_eq!(project.clips[0].video, expect("videos/a.mp4"));
+        assert_eq!(project.rounds[0].clips[0].video, expect("videos/b.mp4"));
+        assert_eq!(project.rounds[0].background, Some(expect("assets/r.png")));
+        assert_eq!(project.output.path, expect("render/out.mp4"));
+
+        let intro = project.intro.unwrap();
+        assert_eq!(intro.background, expect("assets/intro.png"));
+        assert_eq!(intro.music, "/abs/intro.mp3");
+    }
+
+    #[test]
+    fn file_in_working_dir_keeps_paths_unchanged() {
+        let mut project = parse_project(JSON).unwrap();
+        let before = project.clone();
+
+        project.resolve_paths(&project_dir("friday.json"));
+        assert_eq!(project, before);
+    }
+}
+
 #[cfg(test)]
 mod parsing_tests {
     use super::*;
@@ -90,6 +230,56 @@ mod parsing_tests {
         load_project_from_reader(s.as_bytes())
     }
 
+    #[test]
+    fn rejects_invalid_timecodes_at_load_time() {
+        let json = r#"{
+      "output": { "path": "render/out.mp4" },
+      "timings": { "guess_duration": "10s", "reveal_duration": "00:00:05.000" },
+      "clips": [ { "video": "videos/a.mp4", "start": "banana", "answer": "A" } ]
+    }"#;
+
+        let err = parse(json).unwrap_err();
+        let full = format!("{:#}", err);
+        assert!(
+            full.contains("invalid timecode 'banana'"),
+            "error was:\n{full}"
+        );
+
+        let json_err = err.downcast_ref::<serde_json::Error>().unwrap();
+        assert_eq!(json_err.line(), 4);
+    }
+
+    #[test]
+    fn upgrades_legacy_documents() {
+        let json = r#"
+    {
+      "output": { "path": "render/out.mp4" },
+      "timings": { "guess_duration": "00:00:10.000", "reveal_duration": "00:00:05.000" },
+      "clips": [ { "video": "videos/a.mp4", "start": "00:00:01.000", "answer": "A" } ]
+    }
+    "#;
+
+        let project = parse(json).unwrap();
+        assert_eq!(project.version, CURRENT_VERSION);
+
+        let written = serde_json::to_value(&project).unwrap();
+        assert_eq!(written["version"], CURRENT_VERSION);
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let json = format!(
+            r#"{{ "version": {}, "output": {{ "path": "x.mp4" }} }}"#,
+            CURRENT_VERSION + 1
+        );
+
+        let full = format!("{:#}", parse(&json).unwrap_err());
+        assert!(
+            full.contains("please update blindtest"),
+            "error was:\n{full}"
+        );
+    }
+
     #[test]
     fn fails_on_unknown_fields() {
         let json = r#"
@@ -109,4 +299,124 @@ mod parsing_tests {
         let full = format!("{:#}", err);
         assert!(full.contains("unknown field"), "error was:\n{full}");
     }
+
+    #[test]
+    fn accepts_schema_reference() {
+        let json = r#"
+    {
+      "$schema": "./blindtest.schema.json",
+      "version": 2,
+      "output": { "path": "render/out.mp4" },
+      "timings": { "guess_duration": "10s", "reveal_duration": "5s" }
+    }
+    "#;
+
+        let p = parse(json).unwrap();
+        assert_eq!(p.schema.as_deref(), Some("./blindtest.schema.json"));
+        let out = serde_json::to_string(&p).unwrap();
+        assert!(
+            out.starts_with(r#"{"$schema":"./blindtest.schema.json","#),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn accepts_text_or_structured_answers() {
+        let json = r#"
+    {
+      "output": { "path": "render/out.mp4" },
+      "timings": { "guess_duration": "10s", "reveal_duration": "5s" },
+      "clips": [
+        { "video": "a.mp4", "start": "0s", "answer": "Queen - Bohemian Rhapsody" },
+        { "video": "b.mp4", "start": "0s",
+          "answer": { "artist": "A-ha", "title": "Take On Me", "year": 1985, "note": "Clip culte" } }
+      ]
+    }
+    "#;
+
+        let p = parse(json).unwrap();
+        assert_eq!(p.clips[0].answer.lines(), ["Queen - Bohemian Rhapsody"]);
+        assert_eq!(p.clips[1].answer.summary(), "A-ha - Take On Me");
+        assert_eq!(
+            p.clips[1].answer.lines(),
+            ["A-ha", "Take On Me", "1985", "Clip culte"]
+        );
+
+        // Both forms are written back as they were read
+        let out = serde_json::to_string(&p.clips).unwrap();
+        assert!(
+            out.contains(r#""answer":"Queen - Bohemian Rhapsody""#),
+            "{out}"
+        );
+        assert!(
+            out.contains(
+                r#""answer":{"artist":"A-ha","title":"Take On Me","year":1985,"note":"Clip culte"}"#
+            ),
+            "{out}"
+        );
+
+        let typo = json.replace("\"artist\"", "\"artits\"");
+        let err = format!("{:#}", parse(&typo).unwrap_err());
+        assert!(err.contains("unknown field `artits`"), "{err}");
+    }
+
+    #[test]
+    fn loads_yaml_and_toml_by_extension() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let yaml = dir.path().join("montage.yaml");
+        std::fs::write(
+            &yaml,
+            "version: 2\n\
+             output: { path: out.mp4 }\n\
+             timings: { guess_duration: 10s, reveal_duration: 5s }\n\
+             clips:\n\
+             \x20 # backup if the chorus is too easy\n\
+             \x20 - { video: a.mp4, start: 1m30s, answer: A }\n",
+        )
+        .unwrap();
+        let p = load_project(&yaml).unwrap();
+        assert_eq!(p.clips[0].answer.summary(), "A");
+        assert_eq!(p.clips[0].video, dir.path().join("a.mp4").to_string_lossy());
+
+        let toml = dir.path().join("montage.toml");
+        std::fs::write(
+            &toml,
+            "[output]\npath = \"out.mp4\"\n\n\
+             [timings]\nguess_duration = \"10s\"\nreveal_duration = \"5s\"\n\n\
+             [[clips]]\nvideo = \"a.mp4\"\nstart = \"00:01:30\"\nanswer = \"A\"\n\
+             surprise = true\n",
+        )
+        .unwrap();
+        let err = format!("{:#}", load_project(&toml).unwrap_err());
+        assert!(err.contains("invalid TOML in file"), "{err}");
+        assert!(err.contains("unknown field"), "{err}");
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        let json = r#"
+    {
+      "version": 2,
+      "output": { "path": "out.mp4" },
+      "timings": { "guess_duration": "10s", "reveal_duration": "5s" },
+      "clips": [ { "video": "a.mp4", "start": "00:00:01:12", "answer": "A", "end": "20s" } ],
+      "rounds": [
+        { "title": "R", "clips": [ { "video": "b.mp4", "start": "2s", "answer": "B" } ] }
+      ]
+    }
+    "#;
+        let project = parse(json).unwrap();
+
+        for format in [
+            ProjectFormat::Json,
+            ProjectFormat::Yaml,
+            ProjectFormat::Toml,
+        ] {
+            let text = format.to_string_pretty(&project).unwrap();
+            let back = parse_project_as(&text, format)
+                .unwrap_or_else(|e| panic!("{}: {e:#}\n{text}", format.name()));
+            assert_eq!(back, project, "{}", format.name());
+        }
+    }
 }
@@ -1,10 +1,21 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
-use blindtest::ffmpeg_command::build_ffmpeg_command;
-use blindtest::validate::validate_project;
-use blindtest::{ffmpeg, load_project};
+use blindtest::ffmpeg_command::{
+    CommandSpec, build_ffmpeg_command, embed_chapters, expected_duration_ms,
+};
+use blindtest::import::CsvColumns;
+use blindtest::model::{CURRENT_VERSION, Project};
+use blindtest::validate::{
+    Issue, ValidationReport, check_media as check_media_report, check_project, check_theme_files,
+    validate_media, validate_project,
+};
+use blindtest::{
+    ProjectFormat, answers, chapters, diagnostic, ffmpeg, import, migrate, parse_project_as, probe,
+    project_dir, read_project_source, segments,
+};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -30,20 +41,243 @@ struct Cli {
 enum Commands {
     #[command(
         about = "Rendre une vidéo à partir d'un fichier JSON",
-        long_about = "Lit un fichier JSON (format V1), valide le projet et lance FFmpeg.\n\n\
+        long_about = "Lit un fichier projet JSON, YAML ou TOML (toutes versions du format), valide le projet et lance FFmpeg.\n\n\
+Avec --segments, chaque clip (et l'intro, les manches, l'écran de fin) est rendu\n\
+dans un fichier intermédiaire mis en cache : après une petite modification,\n\
+seuls les segments modifiés sont reconstruits.\n\
+Avec --jobs N, jusqu'à N segments sont rendus en parallèle (le résultat est\n\
+identique quel que soit N).\n\n\
+Les chemins relatifs du JSON (vidéos, images, musiques, sortie) sont résolus\n\
+depuis le dossier du fichier JSON, sauf avec --paths-from-cwd.\n\n\
 Exemples :\n\
   blindtest render montage.json\n\
-  blindtest render montage.json --dry-run\n"
+  blindtest render montage.json --dry-run\n\
+  blindtest render montage.json --segments\n\
+  blindtest render montage.json --jobs 8\n"
     )]
     Render {
         #[arg(
             value_name = "JSON",
-            help = "Chemin vers le fichier JSON (ex: montage.json)"
+            help = "Chemin vers le fichier projet (ex: montage.json, montage.yaml, montage.toml)"
         )]
         input: PathBuf,
 
         #[arg(long, help = "Affiche la commande FFmpeg sans lancer le rendu")]
         dry_run: bool,
+
+        #[arg(
+            long,
+            help = "Rendu par segments avec cache (ne reconstruit que les clips modifiés)"
+        )]
+        segments: bool,
+
+        #[arg(
+            long,
+            value_name = "DOSSIER",
+            help = "Dossier du cache des segments, active --segments (défaut : .blindtest-cache à côté de la vidéo)"
+        )]
+        cache_dir: Option<PathBuf>,
+
+        #[arg(
+            long,
+            short = 'j',
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..),
+            help = "Nombre de segments rendus en parallèle (active --segments)"
+        )]
+        jobs: Option<u32>,
+
+        #[arg(
+            long,
+            help = "Chemins relatifs résolus depuis le dossier courant (et non depuis le dossier du JSON)"
+        )]
+        paths_from_cwd: bool,
+
+        #[arg(
+            long,
+            help = "Intègre les chapitres (intro, manches, extraits) dans la vidéo"
+        )]
+        chapters: bool,
+    },
+
+    #[command(
+        about = "Vérifier un fichier JSON et lister tous les problèmes",
+        long_about = "Valide le projet (format, durées, timecodes) puis, si ffprobe est disponible,\n\
+les fichiers médias. Toutes les erreurs et tous les avertissements sont listés\n\
+avec leur chemin JSON (ex: clips[7].start).\n\n\
+Code de sortie : 0 si le projet est valide, 1 sinon.\n\n\
+Exemples :\n\
+  blindtest validate montage.json\n\
+  blindtest validate montage.json --format json\n"
+    )]
+    Validate {
+        #[arg(
+            value_name = "JSON",
+            help = "Chemin vers le fichier projet (ex: montage.json, montage.yaml, montage.toml)"
+        )]
+        input: PathBuf,
+
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = ReportFormat::Text,
+            help = "Format de sortie"
+        )]
+        format: ReportFormat,
+
+        #[arg(long, help = "Ne pas vérifier les fichiers médias (ffprobe)")]
+        no_media: bool,
+
+        #[arg(
+            long,
+            help = "Chemins relatifs résolus depuis le dossier courant (et non depuis le dossier du JSON)"
+        )]
+        paths_from_cwd: bool,
+    },
+
+    #[command(
+        about = "Mettre à jour des fichiers JSON vers la dernière version du format",
+        long_about = "Met chaque fichier au format le plus récent (champ `version`).\n\
+Seul le champ `version` est modifié : commentaires et mise en forme sont conservés.\n\
+Les anciens fichiers restent lisibles sans migration : ils sont mis à jour\n\
+à la volée au chargement.\n\n\
+Exemples :\n\
+  blindtest migrate montage.json\n\
+  blindtest migrate shows/*.json\n\
+  blindtest migrate montage.json --dry-run\n"
+    )]
+    Migrate {
+        #[arg(
+            value_name = "JSON",
+            required = true,
+            help = "Fichiers projet (JSON, YAML ou TOML) à mettre à jour"
+        )]
+        inputs: Vec<PathBuf>,
+
+        #[arg(long, help = "Affiche le résultat sans modifier les fichiers")]
+        dry_run: bool,
+    },
+
+    #[command(
+        about = "Exporter la feuille de réponses pour l'animateur",
+        long_about = "Liste les réponses dans l'ordre, avec l'instant de la vidéo finale où commence\n\
+chaque devinette et chaque révélation (intro et cartons de manche compris).\n\n\
+Le format est déduit de l'extension du fichier de sortie (.md, .csv, .html, .txt),\n\
+texte par défaut.\n\n\
+Exemples :\n\
+  blindtest answers montage.json\n\
+  blindtest answers montage.json -o reponses.html\n\
+  blindtest answers montage.json --format markdown\n"
+    )]
+    Answers {
+        #[arg(
+            value_name = "PROJET",
+            help = "Chemin vers le fichier projet (ex: montage.json, montage.yaml, montage.toml)"
+        )]
+        input: PathBuf,
+
+        #[arg(long, value_enum, help = "Format de la feuille")]
+        format: Option<SheetFormat>,
+
+        #[arg(
+            short,
+            long,
+            value_name = "FICHIER",
+            help = "Écrit la feuille dans un fichier au lieu de la sortie standard"
+        )]
+        output: Option<PathBuf>,
+    },
+
+    #[command(
+        about = "Exporter les sous-titres des réponses (SRT ou WebVTT)",
+        long_about = "Crée un fichier de sous-titres qui affiche chaque réponse pendant sa révélation,\n\
+à publier à côté de la vidéo.\n\n\
+Le format est déduit de l'extension du fichier de sortie (.srt, .vtt), SRT par défaut.\n\n\
+Exemples :\n\
+  blindtest subtitles montage.json -o blindtest.srt\n\
+  blindtest subtitles montage.json -o blindtest.vtt\n"
+    )]
+    Subtitles {
+        #[arg(
+            value_name = "PROJET",
+            help = "Chemin vers le fichier projet (ex: montage.json, montage.yaml, montage.toml)"
+        )]
+        input: PathBuf,
+
+        #[arg(long, value_enum, help = "Format des sous-titres")]
+        format: Option<SubtitleFormat>,
+
+        #[arg(
+            short,
+            long,
+            value_name = "FICHIER",
+            help = "Écrit les sous-titres dans un fichier au lieu de la sortie standard"
+        )]
+        output: Option<PathBuf>,
+    },
+
+    #[command(
+        about = "Exporter les chapitres de la vidéo (YouTube ou ffmetadata)",
+        long_about = "Liste les chapitres de la vidéo finale : intro, un par carton de manche, un par\n\
+extrait et écran de fin. Les extraits s'appellent « Extrait N » pour ne pas\n\
+dévoiler les réponses, sauf avec --with-answers.\n\n\
+- youtube : lignes « MM:SS Titre » à coller dans la description ; les chapitres\n\
+  de moins de 10 s (intro, cartons) sont fusionnés avec le suivant ; un\n\
+  avertissement s'affiche s'il en reste moins de 3 (YouTube ignorerait la liste).\n\
+- ffmetadata : fichier de métadonnées FFmpeg (voir aussi `render --chapters`).\n\n\
+Exemples :\n\
+  blindtest chapters montage.json\n\
+  blindtest chapters montage.json --with-answers -o chapitres.txt\n\
+  blindtest chapters montage.json --format ffmetadata -o chapitres.ffmetadata\n"
+    )]
+    Chapters {
+        #[arg(
+            value_name = "PROJET",
+            help = "Chemin vers le fichier projet (ex: montage.json, montage.yaml, montage.toml)"
+        )]
+        input: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = ChapterFormat::Youtube, help = "Format des chapitres")]
+        format: ChapterFormat,
+
+        #[arg(long, help = "Utilise les réponses comme titres des extraits")]
+        with_answers: bool,
+
+        #[arg(
+            short,
+            long,
+            value_name = "FICHIER",
+            help = "Écrit les chapitres dans un fichier au lieu de la sortie standard"
+        )]
+        output: Option<PathBuf>,
+    },
+
+    #[command(
+        about = "Créer un projet à partir d'une liste existante (CSV, ...)",
+        arg_required_else_help = true
+    )]
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+
+    #[command(
+        about = "Afficher le JSON Schema des fichiers projet",
+        long_about = "Génère le JSON Schema du format de projet (champs, timecodes, résolution).\n\
+Les éditeurs (VS Code, ...) l'utilisent pour l'autocomplétion et la validation\n\
+pendant la saisie.\n\n\
+Exemples :\n\
+  blindtest schema\n\
+  blindtest schema -o blindtest.schema.json\n"
+    )]
+    Schema {
+        #[arg(
+            short,
+            long,
+            value_name = "FICHIER",
+            help = "Écrit le schéma dans un fichier au lieu de la sortie standard"
+        )]
+        output: Option<PathBuf>,
     },
 
     #[command(
@@ -99,25 +333,451 @@ Exemples :\n\
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum ImportSource {
+    #[command(
+        about = "Importer les clips d'un fichier CSV (export de tableur)",
+        long_about = "Crée un projet à partir d'un CSV avec une ligne d'en-tête et les colonnes\n\
+video, start, answer et (optionnelle) round. Les clips d'une même manche sont\n\
+regroupés ; les autres réglages reprennent les valeurs du mode rapide.\n\
+Le séparateur (; tabulation ou ,) est détecté automatiquement.\n\n\
+Exemples :\n\
+  blindtest import csv liste.csv\n\
+  blindtest import csv liste.csv -o soiree.yaml\n\
+  blindtest import csv liste.csv --column video=Fichier --column answer=Titre\n"
+    )]
+    Csv {
+        #[arg(value_name = "CSV", help = "Fichier CSV à importer")]
+        input: PathBuf,
+
+        #[arg(
+            short,
+            long,
+            value_name = "FICHIER",
+            help = "Projet à générer (.json, .yaml ou .toml), par défaut à côté du CSV"
+        )]
+        output: Option<PathBuf>,
+
+        #[arg(
+            long = "column",
+            value_name = "CHAMP=COLONNE",
+            help = "Nom de la colonne d'un champ (video, start, answer, round), ex: answer=Titre"
+        )]
+        columns: Vec<String>,
+
+        #[arg(long, value_name = "CARACTÈRE", help = "Séparateur de colonnes")]
+        delimiter: Option<char>,
+
+        #[arg(long, help = "Remplace le projet s'il existe déjà")]
+        force: bool,
+    },
+
+    #[command(
+        about = "Importer les entrées d'une playlist M3U ou PLS",
+        long_about = "Crée un projet avec un clip par entrée de la playlist (.m3u, .m3u8 ou .pls).\n\
+La réponse est le titre de l'entrée (#EXTINF, TitleN=) ou, à défaut, le nom\n\
+du fichier. Les chemins relatifs sont résolus depuis le dossier de la playlist.\n\n\
+Exemples :\n\
+  blindtest import playlist soiree.m3u\n\
+  blindtest import playlist soiree.pls -o shows/soiree.json\n"
+    )]
+    Playlist {
+        #[arg(
+            value_name = "PLAYLIST",
+            help = "Playlist à importer (.m3u, .m3u8, .pls)"
+        )]
+        input: PathBuf,
+
+        #[arg(
+            short,
+            long,
+            value_name = "FICHIER",
+            help = "Projet à générer (.json, .yaml ou .toml), par défaut à côté de la playlist"
+        )]
+        output: Option<PathBuf>,
+
+        #[arg(long, help = "Remplace le projet s'il existe déjà")]
+        force: bool,
+    },
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ReportFormat {
+    /// Lisible dans un terminal
+    Text,
+    /// JSON (pour les éditeurs / outils)
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum SheetFormat {
+    /// Colonnes alignées
+    Text,
+    /// Tableaux Markdown
+    Markdown,
+    /// Tableur (horodatages exacts)
+    Csv,
+    /// Page à imprimer
+    Html,
+}
+
+impl SheetFormat {
+    /// Format matching the extension of an output file, if known.
+    fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_ascii_lowercase();
+        match ext.as_str() {
+            "txt" => Some(SheetFormat::Text),
+            "md" | "markdown" => Some(SheetFormat::Markdown),
+            "csv" => Some(SheetFormat::Csv),
+            "html" | "htm" => Some(SheetFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum SubtitleFormat {
+    /// SubRip (.srt)
+    Srt,
+    /// WebVTT (.vtt)
+    Vtt,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ChapterFormat {
+    /// Liste pour une description YouTube
+    Youtube,
+    /// Fichier de métadonnées FFmpeg
+    Ffmetadata,
+}
+
+/// `blindtest validate --format json` output
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    valid: bool,
+    errors: usize,
+    warnings: usize,
+    issues: &'a [Issue],
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Render { input, dry_run } => {
-            let project = load_project(&input)?;
-            validate_project(&project)?;
+        Commands::Render {
+            input,
+            dry_run,
+            segments,
+            cache_dir,
+            jobs,
+            paths_from_cwd,
+            chapters,
+        } => {
+            let project = load_checked_project(&input, !dry_run, !paths_from_cwd)?;
 
-            let spec = build_ffmpeg_command(&project)?;
+            // Chapters file written next to the output video
+            let chapters_path = chapters.then(|| {
+                Path::new(project.output.path.trim())
+                    .with_extension("ffmetadata")
+                    .to_string_lossy()
+                    .into_owned()
+            });
+            if let Some(path) = chapters_path.as_deref()
+                && !dry_run
+            {
+                write_chapters_file(&project, path)?;
+            }
+
+            if segments || jobs.is_some() || cache_dir.is_some() {
+                let cache_dir = cache_dir.unwrap_or_else(|| segments::default_cache_dir(&project));
+                let mut plan = segments::plan_segments(&project, &cache_dir)?;
+                if let Some(path) = chapters_path.as_deref() {
+                    embed_chapters(&mut plan.concat, path);
+                }
+
+                if dry_run {
+                    for seg in &plan.segments {
+                        let status = if seg.is_cached() {
+                            "en cache"
+                        } else {
+                            "à rendre"
+                        };
+                        println!("# {} ({status})", seg.name);
+                        println!("{}", ffmpeg::format_command(&seg.spec));
+                    }
+                    println!("# concat");
+                    println!("{}", ffmpeg::format_command(&plan.concat));
+                    return Ok(());
+                }
+
+                let rebuilt =
+                    segments::render_segments(&plan, jobs.unwrap_or(1) as usize, print_progress);
+                eprintln!();
+                let rebuilt = rebuilt?;
+                println!(
+                    "🧩 Segments reconstruits : {rebuilt}/{}",
+                    plan.segments.len()
+                );
+                println!("🎬 Vidéo générée : {}", project.output.path);
+                return Ok(());
+            }
+
+            let mut spec = build_ffmpeg_command(&project)?;
+            if let Some(path) = chapters_path.as_deref() {
+                embed_chapters(&mut spec, path);
+            }
 
             if dry_run {
                 println!("{}", ffmpeg::format_command(&spec));
                 return Ok(());
             }
 
-            ffmpeg::run(&spec)?;
+            render_with_progress(&spec, &project)?;
             println!("🎬 Vidéo générée : {}", project.output.path);
         }
 
+        Commands::Validate {
+            input,
+            format,
+            no_media,
+            paths_from_cwd,
+        } => {
+            let file_format = ProjectFormat::from_path(&input);
+            let (report, source) = match read_project_source(&input) {
+                Ok(source) => {
+                    let mut report = match parse_project_as(&source, file_format) {
+                        Ok(mut project) => {
+                            if !paths_from_cwd {
+                                project.resolve_paths(&project_dir(&input));
+                            }
+                            let mut report = check_project(&project);
+                            if !report.has_errors() {
+                                report.merge(check_theme_files(&project));
+                            }
+                            if !no_media && !report.has_errors() && probe::is_available() {
+                                report.merge(check_media_report(&project));
+                            }
+                            report
+                        }
+                        Err(e) => ValidationReport {
+                            issues: vec![diagnostic::load_error_issue(&e, &source)],
+                        },
+                    };
+                    if file_format == ProjectFormat::Json {
+                        report.locate(&source);
+                    }
+                    (report, source)
+                }
+                Err(e) => (
+                    ValidationReport {
+                        issues: vec![diagnostic::load_error_issue(&e, "")],
+                    },
+                    String::new(),
+                ),
+            };
+
+            match format {
+                ReportFormat::Text => print_report(&report, &input.display().to_string(), &source),
+                ReportFormat::Json => {
+                    let json = JsonReport {
+                        valid: !report.has_errors(),
+                        errors: report.errors().count(),
+                        warnings: report.warnings().count(),
+                        issues: &report.issues,
+                    };
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                }
+            }
+
+            if report.has_errors() {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Migrate { inputs, dry_run } => {
+            let mut failed = false;
+
+            for input in &inputs {
+                let file_name = input.display().to_string();
+                let source = read_project_source(input)?;
+                let file_format = ProjectFormat::from_path(input);
+
+                let project = match parse_project_as(&source, file_format) {
+                    Ok(project) => project,
+                    Err(e) => {
+                        let issue = diagnostic::load_error_issue(&e, &source);
+                        eprint!("{}", diagnostic::render(&issue, &file_name, &source));
+                        failed = true;
+                        continue;
+                    }
+                };
+                let Some(from) = migrate::document_version(&source, file_format) else {
+                    eprintln!("❌ {file_name} : champ version illisible");
+                    failed = true;
+                    continue;
+                };
+
+                // Only the version key is rewritten, keeping comments and layout
+                let text = migrate::upgrade_text(&source, file_format);
+                if dry_run {
+                    let text = match text {
+                        Some(text) => text,
+                        None => file_format.to_string_pretty(&project)?,
+                    };
+                    println!("{}", text.trim_end());
+                } else if from == CURRENT_VERSION {
+                    println!("✅ {file_name} : déjà au format v{CURRENT_VERSION}");
+                } else {
+                    match text {
+                        Some(text) => std::fs::write(input, text)
+                            .with_context(|| format!("Impossible d'écrire {file_name}"))?,
+                        // No comments to lose in JSON
+                        None if file_format == ProjectFormat::Json => {
+                            blindtest::wizard::write_project_json(input, &project)?
+                        }
+                        None => {
+                            eprintln!(
+                                "❌ {file_name} : la mise à jour demande de réécrire tout le fichier, \
+ce qui effacerait ses commentaires ; reportez à la main le résultat de --dry-run"
+                            );
+                            failed = true;
+                            continue;
+                        }
+                    }
+                    println!("✅ {file_name} : v{from} → v{CURRENT_VERSION}");
+                }
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Subtitles {
+            input,
+            format,
+            output,
+        } => {
+            let project = load_checked_project(&input, false, false)?;
+
+            let vtt = output
+                .as_deref()
+                .and_then(Path::extension)
+                .is_some_and(|e| e.eq_ignore_ascii_case("vtt"));
+            let subtitles = match format.unwrap_or(if vtt {
+                SubtitleFormat::Vtt
+            } else {
+                SubtitleFormat::Srt
+            }) {
+                SubtitleFormat::Srt => chapters::srt(&project)?,
+                SubtitleFormat::Vtt => chapters::webvtt(&project)?,
+            };
+            write_or_print(&subtitles, output.as_deref(), "Sous-titres")?;
+        }
+
+        Commands::Chapters {
+            input,
+            format,
+            with_answers,
+            output,
+        } => {
+            let project = load_checked_project(&input, false, false)?;
+
+            let list = match format {
+                ChapterFormat::Youtube => {
+                    let count = chapters::youtube_chapters(&project, with_answers)?.len();
+                    if count < chapters::YOUTUBE_MIN_CHAPTERS {
+                        eprintln!(
+                            "⚠️  {count} chapitre(s) seulement : YouTube en exige au moins {} \
+de 10 s ou plus et ignorera cette liste",
+                            chapters::YOUTUBE_MIN_CHAPTERS
+                        );
+                    }
+                    chapters::youtube(&project, with_answers)?
+                }
+                ChapterFormat::Ffmetadata => chapters::ffmetadata(&project, with_answers)?,
+            };
+            write_or_print(&list, output.as_deref(), "Chapitres")?;
+        }
+
+        Commands::Answers {
+            input,
+            format,
+            output,
+        } => {
+            let project = load_checked_project(&input, false, false)?;
+
+            let format = format
+                .or_else(|| output.as_deref().and_then(SheetFormat::from_path))
+                .unwrap_or(SheetFormat::Text);
+            let sheet = match format {
+                SheetFormat::Text => answers::text(&project)?,
+                SheetFormat::Markdown => answers::markdown(&project)?,
+                SheetFormat::Csv => answers::csv(&project)?,
+                SheetFormat::Html => answers::html(&project)?,
+            };
+
+            write_or_print(&sheet, output.as_deref(), "Feuille de réponses")?;
+        }
+
+        Commands::Import { source } => match source {
+            ImportSource::Csv {
+                input,
+                output,
+                columns,
+                delimiter,
+                force,
+            } => {
+                let mut mapping = CsvColumns::default();
+                for m in &columns {
+                    mapping.set(m)?;
+                }
+
+                let text = std::fs::read_to_string(&input)
+                    .with_context(|| format!("failed to read CSV file: {}", input.display()))?;
+                let delimiter = match delimiter {
+                    Some(c) if c.is_ascii() => c as u8,
+                    Some(c) => {
+                        bail!("Séparateur invalide : '{c}' (un caractère ASCII est attendu)")
+                    }
+                    None => import::sniff_delimiter(&text),
+                };
+
+                let project = import::import_csv(text.as_bytes(), &mapping, delimiter)
+                    .with_context(|| format!("invalid CSV file: {}", input.display()))?;
+                let output = output.unwrap_or_else(|| input.with_extension("json"));
+                write_imported_project(&project, &output, force)?;
+            }
+
+            ImportSource::Playlist {
+                input,
+                output,
+                force,
+            } => {
+                let bytes = std::fs::read(&input)
+                    .with_context(|| format!("failed to read playlist: {}", input.display()))?;
+                // .m3u files are often Latin-1: keep what can be read
+                let text = String::from_utf8_lossy(&bytes);
+                let output = output.unwrap_or_else(|| input.with_extension("json"));
+
+                // Relative entries stay relative when the project sits next to the playlist
+                let base = if project_dir(&output) == project_dir(&input) {
+                    PathBuf::new()
+                } else {
+                    project_dir(std::path::absolute(&input)?)
+                };
+
+                let project = import::import_playlist(&text, &base)
+                    .with_context(|| format!("invalid playlist: {}", input.display()))?;
+                write_imported_project(&project, &output, force)?;
+            }
+        },
+
+        Commands::Schema { output } => {
+            let json = blindtest::schema::project_schema_json();
+            write_or_print(&json, output.as_deref(), "Schéma écrit")?;
+        }
+
         Commands::New {
             quick,
             shuffle,
@@ -139,10 +799,10 @@ fn main() -> Result<()> {
             println!("✅ JSON généré : {}", json_path);
 
             if quick && !only_json {
-                if let Some(parent) = std::path::Path::new(&project.output.path).parent() {
-                    if !parent.as_os_str().is_empty() {
-                        std::fs::create_dir_all(parent).ok();
-                    }
+                if let Some(parent) = std::path::Path::new(&project.output.path).parent()
+                    && !parent.as_os_str().is_empty()
+                {
+                    std::fs::create_dir_all(parent).ok();
                 }
 
                 let spec = build_ffmpeg_command(&project)?;
@@ -152,7 +812,8 @@ fn main() -> Result<()> {
                     return Ok(());
                 }
 
-                ffmpeg::run(&spec)?;
+                check_media(&project)?;
+                render_with_progress(&spec, &project)?;
                 println!("🎬 Vidéo générée : {}", project.output.path);
             }
         }
@@ -160,3 +821,164 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Write the ffmetadata chapters of a project (creating the output directory if needed).
+fn write_chapters_file(project: &Project, path: &str) -> Result<()> {
+    if let Some(parent) = Path::new(path).parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).ok();
+    }
+    std::fs::write(path, chapters::ffmetadata(project, false)?)
+        .with_context(|| format!("failed to write {path}"))
+}
+
+/// Write a generated file, or print it to stdout without `path`.
+fn write_or_print(content: &str, path: Option<&Path>, label: &str) -> Result<()> {
+    match path {
+        Some(path) => {
+            std::fs::write(path, content)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("✅ {label} : {}", path.display());
+        }
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+/// Validate an imported project and write it, without overwriting an existing file unless `force`.
+fn write_imported_project(project: &Project, output: &Path, force: bool) -> Result<()> {
+    if output.exists() && !force {
+        bail!(
+            "{} existe déjà (utilisez --force pour le remplacer)",
+            output.display()
+        );
+    }
+
+    validate_project(project)?;
+    blindtest::wizard::write_project_json(output, project)?;
+
+    println!(
+        "✅ Projet généré : {} ({} clip(s), {} manche(s))",
+        output.display(),
+        project.all_clips().count(),
+        project.rounds.len()
+    );
+    println!(
+        "   Vérifiez-le avec : blindtest validate {}",
+        output.display()
+    );
+    Ok(())
+}
+
+/// Load, validate and (optionally) check the media of a project before rendering.
+///
+/// Relative paths are resolved against the JSON file's directory if `resolve_paths` is set.
+/// Problems are printed with a source excerpt; on any error the process exits with code 1.
+fn load_checked_project(input: &Path, with_media: bool, resolve_paths: bool) -> Result<Project> {
+    let file_name = input.display().to_string();
+    let source = read_project_source(input)?;
+    let file_format = ProjectFormat::from_path(input);
+
+    let mut project = match parse_project_as(&source, file_format) {
+        Ok(project) => project,
+        Err(e) => {
+            eprint!(
+                "{}",
+                diagnostic::render(
+                    &diagnostic::load_error_issue(&e, &source),
+                    &file_name,
+                    &source
+                )
+            );
+            std::process::exit(1);
+        }
+    };
+
+    if resolve_paths {
+        project.resolve_paths(&project_dir(input));
+    }
+
+    let mut report = check_project(&project);
+    if !report.has_errors() {
+        report.merge(check_theme_files(&project));
+    }
+    if with_media && !report.has_errors() {
+        if probe::is_available() {
+            report.merge(check_media_report(&project));
+        } else {
+            eprintln!("⚠️  ffprobe introuvable : vérification des fichiers médias ignorée");
+        }
+    }
+    if file_format == ProjectFormat::Json {
+        report.locate(&source);
+    }
+
+    for issue in &report.issues {
+        eprint!("{}", diagnostic::render(issue, &file_name, &source));
+    }
+    if report.has_errors() {
+        std::process::exit(1);
+    }
+
+    Ok(project)
+}
+
+fn print_report(report: &ValidationReport, file_name: &str, source: &str) {
+    for issue in &report.issues {
+        println!("{}", diagnostic::render(issue, file_name, source));
+    }
+
+    let (errors, warnings) = (report.errors().count(), report.warnings().count());
+    if errors == 0 && warnings == 0 {
+        println!("✅ Projet valide");
+    } else {
+        println!("{errors} erreur(s), {warnings} avertissement(s)");
+    }
+}
+
+/// Check the theme files, then the media files before rendering
+/// (media skipped with a warning without ffprobe).
+fn check_media(project: &Project) -> Result<()> {
+    check_theme_files(project).into_result()?;
+    if !probe::is_available() {
+        eprintln!("⚠️  ffprobe introuvable : vérification des fichiers médias ignorée");
+        return Ok(());
+    }
+    validate_media(project)
+}
+
+/// Run ffmpeg while showing a progress line (percentage, speed, ETA) on stderr.
+fn render_with_progress(spec: &CommandSpec, project: &Project) -> Result<()> {
+    let total_ms = expected_duration_ms(project)?;
+
+    let result = ffmpeg::run_with_progress(spec, total_ms, print_progress);
+    eprintln!();
+
+    result
+}
+
+/// Print the progress line (percentage, speed, ETA) on stderr, in place.
+fn print_progress(p: &ffmpeg::Progress) {
+    let speed = p
+        .speed
+        .map(|s| format!("x{s:.2}"))
+        .unwrap_or_else(|| "x?".into());
+    let eta = p
+        .eta()
+        .map(|d| format_hms(d.as_secs()))
+        .unwrap_or_else(|| "--:--".into());
+    eprint!(
+        "\r🎞️  {:5.1} % | {speed} | ETA {eta}   ",
+        p.fraction() * 100.0
+    );
+}
+
+fn format_hms(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m:02}:{s:02}")
+    }
+}
@@ -0,0 +1,316 @@
+// src/migrate.rs
+
+use anyhow::{Result, bail};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::format::ProjectFormat;
+use crate::model::{CURRENT_VERSION, LEGACY_VERSION};
+
+/// One upgrade step: turns a version `n` document into a version `n + 1` one.
+/// Returns true if it changed anything besides the version number.
+type Step = fn(&mut Map<String, Value>) -> Result<bool>;
+
+/// `STEPS[i]` upgrades version `LEGACY_VERSION + i` to the next one.
+///
+/// Every change of the format (new field, new accepted value) bumps
+/// [`CURRENT_VERSION`] and adds a step, a no-op one when older documents stay
+/// valid: an older blindtest then asks to be updated instead of failing on an
+/// unknown field. The `format_changes_bump_the_version` test enforces it.
+const STEPS: &[Step] = &[v1_to_v2, v2_to_v3];
+
+/// Version 2 only introduces the `version` field itself.
+fn v1_to_v2(_doc: &mut Map<String, Value>) -> Result<bool> {
+    Ok(false)
+}
+
+/// Version 3 adds optional fields only: `$schema`, `quiz`, clip `distractors`,
+/// `hint` and `hint_duration`, structured `answer` and `theme`.
+fn v2_to_v3(_doc: &mut Map<String, Value>) -> Result<bool> {
+    Ok(false)
+}
+
+/// Version declared by a document ([`LEGACY_VERSION`] without a `version` field).
+///
+/// Returns `None` if the document cannot be read far enough to tell
+/// (the full parse reports the actual error).
+pub fn document_version(source: &str, format: ProjectFormat) -> Option<u32> {
+    #[derive(Deserialize)]
+    struct VersionOnly {
+        version: Option<u32>,
+    }
+
+    let doc: VersionOnly = format.parse(source).ok()?;
+    Some(doc.version.unwrap_or(LEGACY_VERSION))
+}
+
+/// `source` upgraded to [`CURRENT_VERSION`] by only writing its `version` key,
+/// so comments, key order and hand-written durations (`1m30s`) are kept.
+///
+/// Returns `None` when that is not enough: the upgrade changes the structure,
+/// or the edited text would not read back as the upgraded project.
+pub fn upgrade_text(source: &str, format: ProjectFormat) -> Option<String> {
+    let expected = crate::parse_project_as(source, format).ok()?;
+    let text = match format {
+        ProjectFormat::Json => set_json_version(source, CURRENT_VERSION)?,
+        ProjectFormat::Yaml | ProjectFormat::Toml => {
+            set_version_line(source, format, CURRENT_VERSION)
+        }
+    };
+
+    let upgraded = crate::parse_project_as(&text, format).ok()?;
+    (document_version(&text, format) == Some(CURRENT_VERSION) && upgraded == expected)
+        .then_some(text)
+}
+
+/// Replace the value of the top-level `version` member, or insert the key
+/// after the opening brace.
+fn set_json_version(source: &str, version: u32) -> Option<String> {
+    if let Some((start, end)) = crate::diagnostic::locate_span(source, "version") {
+        return Some(format!("{}{version}{}", &source[..start], &source[end..]));
+    }
+
+    // Indented like the first key
+    let brace = source.find('{')?;
+    let indent = source[brace + 1..]
+        .lines()
+        .skip(1)
+        .find(|l| !l.trim().is_empty())
+        .map(|l| &l[..l.len() - l.trim_start().len()])
+        .unwrap_or("  ");
+    Some(format!(
+        "{}\n{indent}\"version\": {version},{}",
+        &source[..=brace],
+        &source[brace + 1..]
+    ))
+}
+
+/// YAML and TOML: replace the top-level `version` line (keeping its comment),
+/// or add one after the leading comments.
+fn set_version_line(source: &str, format: ProjectFormat, version: u32) -> String {
+    let toml = format == ProjectFormat::Toml;
+    let new_line = if toml {
+        format!("version = {version}")
+    } else {
+        format!("version: {version}")
+    };
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+
+    // Top-level keys: unindented in YAML, before the first table in TOML
+    let top_end = lines
+        .iter()
+        .position(|l| toml && l.trim_start().starts_with('['))
+        .unwrap_or(lines.len());
+    let found = lines[..top_end].iter().position(|l| {
+        (toml || !l.starts_with(char::is_whitespace))
+            && l.trim_start()
+                .strip_prefix("version")
+                .is_some_and(|rest| rest.trim_start().starts_with(if toml { '=' } else { ':' }))
+    });
+
+    match found {
+        Some(i) => {
+            let comment = lines[i].find(" #").map(|c| lines[i][c..].to_string());
+            lines[i] = new_line + comment.as_deref().unwrap_or("");
+        }
+        None => {
+            let at = lines
+                .iter()
+                .position(|l| {
+                    let l = l.trim();
+                    !(l.is_empty() || l.starts_with('#') || l.starts_with('%') || l == "---")
+                })
+                .unwrap_or(lines.len());
+            lines.insert(at, new_line);
+        }
+    }
+
+    let mut out = lines.join(newline);
+    if source.ends_with('\n') {
+        out.push_str(newline);
+    }
+    out
+}
+
+/// Fail on versions this build does not know about.
+pub fn check_version(version: u32) -> Result<()> {
+    if version > CURRENT_VERSION {
+        bail!(
+            "project format version {version} is newer than this blindtest supports \
+             (up to {CURRENT_VERSION}): please update blindtest"
+        );
+    }
+    if version < LEGACY_VERSION {
+        bail!("unknown project format version {version}");
+    }
+    Ok(())
+}
+
+/// Upgrade a version `from` document to [`CURRENT_VERSION`], in place.
+///
+/// Returns true if the structure changed (the document must then be read
+/// from `doc` rather than from its original text).
+pub fn upgrade(doc: &mut Value, from: u32) -> Result<bool> {
+    check_version(from)?;
+    let Some(obj) = doc.as_object_mut() else {
+        bail!("a project must be an object");
+    };
+
+    let mut changed = false;
+    for step in &STEPS[(from - LEGACY_VERSION) as usize..] {
+        changed |= step(obj)?;
+    }
+    obj.insert("version".into(), Value::from(CURRENT_VERSION));
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    /// Version and fingerprint of the format it describes (see [`format_fingerprint`]).
+    const CURRENT_FORMAT: (u32, &str) = (3, "128c5ee1ea073c3d");
+
+    /// Hash of the project JSON Schema, without its descriptions and examples
+    /// (documentation changes do not change the format).
+    fn format_fingerprint() -> String {
+        fn strip_docs(v: &mut Value) {
+            match v {
+                Value::Object(map) => {
+                    map.retain(|k, _| !matches!(k.as_str(), "description" | "examples" | "title"));
+                    map.values_mut().for_each(strip_docs);
+                }
+                Value::Array(items) => items.iter_mut().for_each(strip_docs),
+                _ => {}
+            }
+        }
+
+        let mut schema = serde_json::to_value(crate::schema::project_schema()).unwrap();
+        strip_docs(&mut schema);
+        let digest = Sha256::digest(schema.to_string().as_bytes());
+        digest[..8].iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn steps_cover_every_version() {
+        assert_eq!(STEPS.len() as u32, CURRENT_VERSION - LEGACY_VERSION);
+    }
+
+    #[test]
+    fn format_changes_bump_the_version() {
+        assert_eq!(
+            CURRENT_FORMAT,
+            (CURRENT_VERSION, format_fingerprint().as_str()),
+            "the project format changed: bump CURRENT_VERSION, add a step to STEPS \
+             (a no-op one if older documents stay valid), then update CURRENT_FORMAT"
+        );
+    }
+
+    #[test]
+    fn reads_document_version() {
+        let json = ProjectFormat::Json;
+        assert_eq!(
+            document_version(r#"{ "output": {} }"#, json),
+            Some(LEGACY_VERSION)
+        );
+        assert_eq!(document_version(r#"{ "version": 2 }"#, json), Some(2));
+        assert_eq!(document_version("{ not json", json), None);
+        assert_eq!(
+            document_version("version: 2\n", ProjectFormat::Yaml),
+            Some(2)
+        );
+        assert_eq!(
+            document_version("[output]\npath = \"x.mp4\"\n", ProjectFormat::Toml),
+            Some(LEGACY_VERSION)
+        );
+    }
+
+    #[test]
+    fn upgrades_v2_document_unchanged() {
+        let mut doc: Value =
+            serde_json::from_str(r#"{ "version": 2, "output": { "path": "x.mp4" } }"#).unwrap();
+
+        assert!(!upgrade(&mut doc, 2).unwrap());
+        assert_eq!(doc["version"], 3);
+        assert_eq!(doc["output"]["path"], "x.mp4");
+    }
+
+    #[test]
+    fn upgrades_legacy_document() {
+        let mut doc: Value = serde_json::from_str(r#"{ "output": { "path": "x.mp4" } }"#).unwrap();
+
+        let changed = upgrade(&mut doc, LEGACY_VERSION).unwrap();
+        assert!(!changed);
+        assert_eq!(doc["version"], CURRENT_VERSION);
+        assert_eq!(doc["output"]["path"], "x.mp4");
+    }
+
+    #[test]
+    fn upgrade_keeps_comments_and_durations() {
+        let yaml = "# Soirée du 12\n\
+output:\n  path: out.mp4 # rendu final\n\
+timings:\n  guess_duration: 1m30s\n  reveal_duration: \"5\"\n\
+clips:\n  - { video: a.mp4, start: \"0:12\", answer: Queen } # ouverture\n";
+        let upgraded = upgrade_text(yaml, ProjectFormat::Yaml).unwrap();
+        assert_eq!(
+            upgraded,
+            format!(
+                "# Soirée du 12\nversion: {CURRENT_VERSION}\n{}",
+                &yaml["# Soirée du 12\n".len()..]
+            )
+        );
+
+        let toml = "version = 2 # ancien format\n\n[output]\npath = \"out.mp4\"\n\n\
+[timings]\nguess_duration = \"10s\"\nreveal_duration = \"5s\"\n";
+        assert_eq!(
+            upgrade_text(toml, ProjectFormat::Toml).unwrap(),
+            toml.replace("version = 2", &format!("version = {CURRENT_VERSION}"))
+        );
+
+        let json = "{\n    \"output\": { \"path\": \"out.mp4\" },\n    \"timings\": \
+{ \"guess_duration\": \"10s\", \"reveal_duration\": \"5s\" }\n}\n";
+        assert_eq!(
+            upgrade_text(json, ProjectFormat::Json).unwrap(),
+            json.replacen('{', &format!("{{\n    \"version\": {CURRENT_VERSION},"), 1)
+        );
+        // Already current: unchanged
+        let current = upgrade_text(json, ProjectFormat::Json).unwrap();
+        assert_eq!(
+            upgrade_text(&current, ProjectFormat::Json).unwrap(),
+            current
+        );
+    }
+
+    #[test]
+    fn upgrade_edits_the_top_level_version_only() {
+        let json = r#"{
+  "clips": [
+    { "video": "a.mp4", "start": "0", "answer": "version" },
+    { "video": "b.mp4", "start": "0", "answer": "Live (\"version\": 1)" }
+  ],
+  "version": 2,
+  "output": { "path": "out.mp4" },
+  "timings": { "guess_duration": "10s", "reveal_duration": "5s" }
+}
+"#;
+        assert_eq!(
+            upgrade_text(json, ProjectFormat::Json).unwrap(),
+            json.replace("\"version\": 2", &format!("\"version\": {CURRENT_VERSION}"))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let err = check_version(CURRENT_VERSION + 1).unwrap_err().to_string();
+        assert!(err.contains("newer than this blindtest"), "{err}");
+        assert!(check_version(0).is_err());
+        assert!(check_version(CURRENT_VERSION).is_ok());
+    }
+}
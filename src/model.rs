@@ -1,21 +1,139 @@
 // src/model.rs
-use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::path::Path;
+
+use crate::timecode::Timecode;
 
 /// Root JSON document
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct Project {
+    /// Optional JSON Schema reference, for editors (ignored by blindtest)
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Format version of the document.
+    /// Documents written before this field existed are version 1.
+    #[serde(default = "legacy_version")]
+    #[schemars(range(min = 1))]
+    pub version: u32,
+
     /// Optional intro shown before the blindtest starts
     #[serde(default)]
     pub intro: Option<Intro>,
 
     pub output: Output,
     pub timings: Timings,
+
+    /// Clips played without any round (before the rounds, if any)
+    #[serde(default)]
     pub clips: Vec<Clip>,
+
+    /// Optional rounds, each one introduced by a title card
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rounds: Vec<Round>,
+
+    /// Optional end screen shown after the last clip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outro: Option<Outro>,
+
+    /// Optional multiple-choice mode: four options are shown during the guess phase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiz: Option<Quiz>,
+
+    /// Optional look of the video: a built-in theme name ("classic", "neon",
+    /// "minimal"), a theme file (.json/.yaml/.toml) or an inline theme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<ThemeRef>,
+}
+
+/// Format version written by this build; older documents are upgraded on load.
+pub const CURRENT_VERSION: u32 = 3;
+
+/// Version of documents without a `version` field.
+pub const LEGACY_VERSION: u32 = 1;
+
+fn legacy_version() -> u32 {
+    LEGACY_VERSION
+}
+
+/// Frame rate used when `output.fps` is not set.
+pub const DEFAULT_FPS: u32 = 30;
+
+impl Project {
+    /// Output frame rate (`output.fps` or [`DEFAULT_FPS`]), also used for SMPTE timecodes.
+    pub fn fps(&self) -> u32 {
+        self.output.fps.unwrap_or(DEFAULT_FPS)
+    }
+
+    /// Every clip in playing order: top-level `clips` first, then each round's clips.
+    pub fn all_clips(&self) -> impl Iterator<Item = &Clip> {
+        self.clips
+            .iter()
+            .chain(self.rounds.iter().flat_map(|r| r.clips.iter()))
+    }
+
+    /// Every clip with its path in the project file (`clips[i]` or
+    /// `rounds[r].clips[i]`), in playing order.
+    pub fn clip_paths(&self) -> Vec<(String, &Clip)> {
+        let mut out: Vec<(String, &Clip)> = Vec::new();
+        for (i, c) in self.clips.iter().enumerate() {
+            out.push((format!("clips[{i}]"), c));
+        }
+        for (ri, round) in self.rounds.iter().enumerate() {
+            for (i, c) in round.clips.iter().enumerate() {
+                out.push((format!("rounds[{ri}].clips[{i}]"), c));
+            }
+        }
+        out
+    }
+
+    /// Rewrite every relative path (media files and output) as relative to `base`,
+    /// typically the directory of the project file. Absolute paths are left untouched.
+    pub fn resolve_paths(&mut self, base: &Path) {
+        if base.as_os_str().is_empty() {
+            return;
+        }
+
+        let resolve = |p: &mut String| {
+            if !p.is_empty() && Path::new(p.as_str()).is_relative() {
+                *p = base.join(p.as_str()).to_string_lossy().into_owned();
+            }
+        };
+
+        if let Some(intro) = &mut self.intro {
+            resolve(&mut intro.background);
+            resolve(&mut intro.music);
+        }
+        for clip in &mut self.clips {
+            resolve(&mut clip.video);
+        }
+        for round in &mut self.rounds {
+            if let Some(bg) = &mut round.background {
+                resolve(bg);
+            }
+            for clip in &mut round.clips {
+                resolve(&mut clip.video);
+            }
+        }
+        if let Some(outro) = &mut self.outro {
+            resolve(&mut outro.background);
+            resolve(&mut outro.music);
+        }
+        match &mut self.theme {
+            Some(ThemeRef::Name(name)) if crate::theme::is_theme_file(name) => resolve(name),
+            Some(ThemeRef::Inline(theme)) => theme.resolve_paths(base),
+            _ => {}
+        }
+        resolve(&mut self.output.path);
+    }
 }
 
 /// Optional intro section
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct Intro {
     /// Background image path (png/jpg)
@@ -27,12 +145,49 @@ pub struct Intro {
     /// Music file played during intro (mp3/wav/...)
     pub music: String,
 
-    /// Intro duration (HH:MM:SS.mmm)
-    pub duration: String,
+    /// Intro duration
+    pub duration: Timecode,
+}
+
+/// Optional outro section (end screen), mirroring the intro
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Outro {
+    /// Background image path (png/jpg)
+    pub background: String,
+
+    /// Text displayed on the end screen (e.g. "Merci d'avoir joué")
+    pub text: String,
+
+    /// Music file played during outro (mp3/wav/...)
+    pub music: String,
+
+    /// Outro duration
+    pub duration: Timecode,
+}
+
+/// One round (section) of the blindtest, e.g. "Années 80"
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Round {
+    /// Title displayed on the round title card
+    pub title: String,
+
+    /// Optional background image path (png/jpg) for the title card.
+    /// A black screen is used when missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+
+    /// Optional title card duration, 3 seconds by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<Timecode>,
+
+    /// Clips of this round
+    pub clips: Vec<Clip>,
 }
 
 /// Output rendering parameters
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct Output {
     /// Path of the final rendered video file
@@ -40,34 +195,344 @@ pub struct Output {
 
     /// Optional output resolution like "1920x1080"
     #[serde(default)]
+    #[schemars(regex(pattern = RESOLUTION_PATTERN))]
     pub resolution: Option<String>,
 
     /// Optional output frames per second
     #[serde(default)]
+    #[schemars(range(min = 1))]
     pub fps: Option<u32>,
 }
 
-/// Global timings applied to every clip
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// Shape of `output.resolution` ("WIDTHxHEIGHT"), as published in the JSON Schema.
+pub const RESOLUTION_PATTERN: &str = r"^[1-9]\d*x[1-9]\d*$";
+
+/// Multiple-choice (QCM) settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Quiz {
+    /// Seed of the random draw (distractors and option order).
+    /// The same seed always renders the same options.
+    #[serde(default)]
+    pub seed: u64,
+}
+
+/// Theme of the project: a name (built-in theme or theme file) or an inline theme.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ThemeRef {
+    Name(String),
+    Inline(Box<Theme>),
+}
+
+/// Fonts, colours, sizes and positions of the rendered texts.
+///
+/// Every field is optional and overrides the theme named by `extends`
+/// ("classic" by default). Colours use the ffmpeg syntax: a name or
+/// `#RRGGBB`, with an optional `@opacity` (e.g. `black@0.6`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    /// Built-in theme this one starts from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
+    /// Font file (.ttf/.otf) used by every text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font: Option<String>,
+
+    /// Colour of the guess/hint screens and of round title cards without background
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+
+    /// Image shown behind the guess/hint screens instead of `background`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guess_image: Option<String>,
+
+    /// Default text colour
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+
+    /// Default text outline colour
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_color: Option<String>,
+
+    /// Default text outline width, in pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_width: Option<u32>,
+
+    /// Intro, outro and round titles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<TextStyle>,
+
+    /// Countdown of the guess phase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub countdown: Option<TextStyle>,
+
+    /// Quiz options of the guess phase (`y` is the first one, the others follow)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<TextStyle>,
+
+    /// Text of the hint phase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<TextStyle>,
+
+    /// Answer of the reveal phase (`size` is the first line, the others are smaller)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<TextStyle>,
+
+    /// Box colour of the correct option at reveal, in quiz mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+}
+
+impl Theme {
+    /// Rewrite the relative font and image paths as relative to `base`.
+    pub fn resolve_paths(&mut self, base: &Path) {
+        for path in [&mut self.font, &mut self.guess_image]
+            .into_iter()
+            .flatten()
+        {
+            if !path.is_empty() && Path::new(path.as_str()).is_relative() {
+                *path = base.join(path.as_str()).to_string_lossy().into_owned();
+            }
+        }
+    }
+}
+
+/// Look of one kind of text; unset fields come from the theme defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TextStyle {
+    /// Font size, in pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1))]
+    pub size: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_color: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub border_width: Option<u32>,
+
+    /// Colour of a box drawn behind the text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub box_color: Option<String>,
+
+    /// Vertical position of the text centre, in percent of the height (0 = top, 100 = bottom)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(max = 100))]
+    pub y: Option<u32>,
+}
+
+/// Global timings applied to every clip (unless overridden by the clip)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct Timings {
     /// Duration of the "guess" phase
-    pub guess_duration: String,
+    pub guess_duration: Timecode,
 
     /// Duration of the "reveal" phase
-    pub reveal_duration: String,
+    pub reveal_duration: Timecode,
+
+    /// Optional "hint" phase between guess and reveal: the clip `hint`,
+    /// or the answer as underscores with letters revealed over time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint_duration: Option<Timecode>,
 }
 
 /// One blindtest item
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct Clip {
     /// Source video file path
     pub video: String,
 
     /// Start timecode in the source video
-    pub start: String,
+    pub start: Timecode,
+
+    /// Answer displayed during the reveal phase: a text, or the artist,
+    /// title, year... displayed on separate lines
+    pub answer: Answer,
+
+    /// Optional end timecode in the source video.
+    /// The reveal phase lasts until `end` (cannot be combined with `reveal_duration`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<Timecode>,
+
+    /// Optional override of `timings.guess_duration` for this clip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guess_duration: Option<Timecode>,
+
+    /// Optional override of `timings.reveal_duration` for this clip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reveal_duration: Option<Timecode>,
+
+    /// Optional override of `timings.hint_duration` for this clip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint_duration: Option<Timecode>,
+
+    /// Optional category hint shown during the hint phase (e.g. "1985 · Pop"),
+    /// instead of the hangman-style answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+
+    /// Wrong options for the multiple-choice mode.
+    /// Missing ones are drawn from the answers of the other clips.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub distractors: Vec<String>,
+}
+
+/// Answer of a clip: a plain text (`"Queen - Bohemian Rhapsody"`) or its details.
+#[derive(Debug, Clone, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Answer {
+    Text(String),
+    Details(AnswerDetails),
+}
+
+/// Answer split into fields, e.g. `{ "artist": "Queen", "title": "Bohemian Rhapsody", "year": 1975 }`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AnswerDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artist: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Release year
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+
+    /// Where the music comes from (film, series, game...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Free comment shown under the answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+// Not `#[serde(untagged)]`: an object with a typo must report the unknown
+// field, not "data did not match any variant".
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AnswerVisitor;
+
+        impl<'de> Visitor<'de> for AnswerVisitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an answer text or an object {artist, title, year, source, note}")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Answer, E> {
+                Ok(Answer::Text(v.to_string()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Answer, A::Error> {
+                AnswerDetails::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(Answer::Details)
+            }
+        }
+
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+// Same reason as `Answer`: typos in an inline theme must report the field.
+impl<'de> Deserialize<'de> for ThemeRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ThemeVisitor;
+
+        impl<'de> Visitor<'de> for ThemeVisitor {
+            type Value = ThemeRef;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a theme name, a theme file or a theme object")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ThemeRef, E> {
+                Ok(ThemeRef::Name(v.to_string()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<ThemeRef, A::Error> {
+                Theme::deserialize(de::value::MapAccessDeserializer::new(map))
+                    .map(|theme| ThemeRef::Inline(Box::new(theme)))
+            }
+        }
+
+        deserializer.deserialize_any(ThemeVisitor)
+    }
+}
+
+impl Default for Answer {
+    fn default() -> Self {
+        Answer::Text(String::new())
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl Answer {
+    /// One-line answer: the text, or "Artist - Title" (quiz options, chapters, hangman...).
+    pub fn summary(&self) -> String {
+        match self {
+            Answer::Text(text) => text.trim().to_string(),
+            Answer::Details(d) => [&d.artist, &d.title]
+                .into_iter()
+                .filter_map(|f| non_blank(f.as_deref()))
+                .collect::<Vec<_>>()
+                .join(" - "),
+        }
+    }
+
+    /// Lines of the reveal screen: the text, or the artist, the title,
+    /// "year · source" and the note (each one only if set).
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            Answer::Text(text) => vec![text.trim().to_string()],
+            Answer::Details(d) => {
+                let year = d.year.map(|y| y.to_string());
+                let when_where: Vec<&str> = [year.as_deref(), d.source.as_deref()]
+                    .into_iter()
+                    .filter_map(non_blank)
+                    .collect();
+                let when_where = when_where.join(" · ");
+                [
+                    non_blank(d.artist.as_deref()),
+                    non_blank(d.title.as_deref()),
+                    non_blank(Some(when_where.as_str())),
+                    non_blank(d.note.as_deref()),
+                ]
+                .into_iter()
+                .flatten()
+                .map(str::to_string)
+                .collect()
+            }
+        }
+    }
+
+    /// No text, or neither artist nor title.
+    pub fn is_blank(&self) -> bool {
+        self.summary().is_empty()
+    }
+}
 
-    /// Answer text displayed during the reveal phase
-    pub answer: String,
+fn non_blank(s: Option<&str>) -> Option<&str> {
+    s.map(str::trim).filter(|s| !s.is_empty())
 }
@@ -0,0 +1,311 @@
+// src/probe.rs
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Media information returned by `ffprobe`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaInfo {
+    /// Container duration, if known (still images have none)
+    pub duration_ms: Option<u64>,
+    /// First video stream, if any
+    pub video: Option<VideoStream>,
+    /// First audio stream, if any
+    pub audio: Option<AudioStream>,
+    /// Container tags (title, artist, ...), keys lowercased
+    pub tags: BTreeMap<String, String>,
+}
+
+impl MediaInfo {
+    pub fn has_audio(&self) -> bool {
+        self.audio.is_some()
+    }
+
+    pub fn has_video(&self) -> bool {
+        self.video.is_some()
+    }
+
+    /// Tag value by (case-insensitive) name, e.g. `title` or `artist`.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+            .filter(|v| !v.trim().is_empty())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoStream {
+    pub codec: String,
+    pub width: u32,
+    pub height: u32,
+    /// Frame rate (from `avg_frame_rate`, falling back to `r_frame_rate`)
+    pub fps: Option<f64>,
+    /// Display rotation in degrees (0, 90, 180, 270)
+    pub rotation: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioStream {
+    pub codec: String,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+}
+
+/// True if `ffprobe` can be found in PATH.
+pub fn is_available() -> bool {
+    which::which("ffprobe").is_ok()
+}
+
+/// Run `ffprobe` on a media file.
+pub fn probe<P: AsRef<Path>>(path: P) -> Result<MediaInfo> {
+    let path = path.as_ref();
+    let program = which::which("ffprobe").context("ffprobe not found in PATH")?;
+
+    let output = Command::new(program)
+        .args([
+            "-v",
+            "error",
+            "-print_format",
+            "json",
+            "-show_format",
+            "-show_streams",
+        ])
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .context("failed to spawn ffprobe")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "ffprobe failed on {} (exit code {:?}). stderr:\n{}",
+            path.display(),
+            output.status.code(),
+            stderr.trim()
+        );
+    }
+
+    let json = String::from_utf8_lossy(&output.stdout);
+    parse_probe_json(&json)
+        .with_context(|| format!("invalid ffprobe output for {}", path.display()))
+}
+
+/// Parse the JSON printed by `ffprobe -print_format json -show_format -show_streams`.
+pub fn parse_probe_json(json: &str) -> Result<MediaInfo> {
+    let raw: RawProbe = serde_json::from_str(json).context("invalid ffprobe JSON")?;
+
+    let duration_ms = raw
+        .format
+        .as_ref()
+        .and_then(|f| f.duration.as_deref())
+        .and_then(parse_seconds_ms);
+
+    let mut video = None;
+    let mut audio = None;
+    for s in &raw.streams {
+        match s.codec_type.as_deref() {
+            Some("video") if video.is_none() => {
+                video = Some(VideoStream {
+                    codec: s.codec_name.clone().unwrap_or_default(),
+                    width: s.width.unwrap_or(0),
+                    height: s.height.unwrap_or(0),
+                    fps: s
+                        .avg_frame_rate
+                        .as_deref()
+                        .and_then(parse_rate)
+                        .or_else(|| s.r_frame_rate.as_deref().and_then(parse_rate)),
+                    rotation: stream_rotation(s),
+                });
+            }
+            Some("audio") if audio.is_none() => {
+                audio = Some(AudioStream {
+                    codec: s.codec_name.clone().unwrap_or_default(),
+                    sample_rate: s.sample_rate.as_deref().and_then(|r| r.parse().ok()),
+                    channels: s.channels,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let tags = raw
+        .format
+        .map(|f| f.tags)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v))
+        .collect();
+
+    Ok(MediaInfo {
+        duration_ms,
+        video,
+        audio,
+        tags,
+    })
+}
+
+// --- raw ffprobe JSON ---
+
+#[derive(Debug, Deserialize)]
+struct RawProbe {
+    #[serde(default)]
+    streams: Vec<RawStream>,
+    #[serde(default)]
+    format: Option<RawFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawFormat {
+    #[serde(default)]
+    duration: Option<String>,
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawStream {
+    #[serde(default)]
+    codec_type: Option<String>,
+    #[serde(default)]
+    codec_name: Option<String>,
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    avg_frame_rate: Option<String>,
+    #[serde(default)]
+    r_frame_rate: Option<String>,
+    #[serde(default)]
+    sample_rate: Option<String>,
+    #[serde(default)]
+    channels: Option<u32>,
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+    #[serde(default)]
+    side_data_list: Vec<RawSideData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSideData {
+    #[serde(default)]
+    rotation: Option<f64>,
+}
+
+/// Rotation from the display matrix side data (recent ffprobe) or the
+/// legacy `rotate` tag, normalized to 0..360.
+fn stream_rotation(s: &RawStream) -> i32 {
+    let deg = s
+        .side_data_list
+        .iter()
+        .find_map(|d| d.rotation)
+        .map(|r| r.round() as i32)
+        .or_else(|| s.tags.get("rotate").and_then(|r| r.parse::<i32>().ok()))
+        .unwrap_or(0);
+    deg.rem_euclid(360)
+}
+
+/// "30000/1001" -> 29.97, "0/0" -> None
+fn parse_rate(s: &str) -> Option<f64> {
+    let (num, den) = s.split_once('/')?;
+    let num: f64 = num.parse().ok()?;
+    let den: f64 = den.parse().ok()?;
+    (den != 0.0 && num > 0.0).then(|| num / den)
+}
+
+/// "12.345000" -> 12345
+fn parse_seconds_ms(s: &str) -> Option<u64> {
+    let secs: f64 = s.trim().parse().ok()?;
+    (secs.is_finite() && secs >= 0.0).then(|| (secs * 1000.0).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIP_JSON: &str = r#"
+    {
+      "streams": [
+        {
+          "index": 0,
+          "codec_name": "h264",
+          "codec_type": "video",
+          "width": 1920,
+          "height": 1080,
+          "r_frame_rate": "30000/1001",
+          "avg_frame_rate": "30000/1001",
+          "side_data_list": [ { "side_data_type": "Display Matrix", "rotation": -90 } ]
+        },
+        {
+          "index": 1,
+          "codec_name": "aac",
+          "codec_type": "audio",
+          "sample_rate": "48000",
+          "channels": 2
+        }
+      ],
+      "format": {
+        "filename": "videos/a.mp4",
+        "duration": "215.480000",
+        "tags": { "TITLE": "One More Time", "artist": "Daft Punk" }
+      }
+    }
+    "#;
+
+    #[test]
+    fn parses_video_with_audio() {
+        let info = parse_probe_json(CLIP_JSON).unwrap();
+
+        assert_eq!(info.duration_ms, Some(215_480));
+        assert!(info.has_audio());
+
+        let v = info.video.unwrap();
+        assert_eq!((v.width, v.height), (1920, 1080));
+        assert!((v.fps.unwrap() - 29.97).abs() < 0.01);
+        assert_eq!(v.rotation, 270);
+
+        let a = info.audio.unwrap();
+        assert_eq!(a.sample_rate, Some(48_000));
+        assert_eq!(a.channels, Some(2));
+
+        assert_eq!(info.tags.get("title").unwrap(), "One More Time");
+    }
+
+    #[test]
+    fn parses_still_image_without_audio() {
+        let json = r#"
+        {
+          "streams": [
+            { "codec_name": "png", "codec_type": "video", "width": 1280, "height": 720,
+              "r_frame_rate": "25/1", "avg_frame_rate": "0/0" }
+          ],
+          "format": { "filename": "assets/intro.png" }
+        }
+        "#;
+
+        let info = parse_probe_json(json).unwrap();
+        assert_eq!(info.duration_ms, None);
+        assert!(!info.has_audio());
+        assert_eq!(info.video.unwrap().fps, Some(25.0));
+    }
+
+    #[test]
+    fn reads_legacy_rotate_tag() {
+        let json = r#"
+        { "streams": [ { "codec_type": "video", "width": 720, "height": 1280,
+                         "tags": { "rotate": "90" } } ] }
+        "#;
+
+        let info = parse_probe_json(json).unwrap();
+        assert_eq!(info.video.unwrap().rotation, 90);
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(parse_probe_json("not json").is_err());
+    }
+}
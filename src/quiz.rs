@@ -0,0 +1,219 @@
+// src/quiz.rs
+
+use crate::draw::{seeded_rng, shuffle};
+use crate::model::{Clip, Project};
+use anyhow::{Result, bail};
+
+/// Number of options shown for each clip (the answer + 3 distractors).
+pub const CHOICES: usize = 4;
+
+/// Vertical distance between two options, in percent of the height.
+pub const OPTION_SPACING: u32 = 12;
+
+/// Highest `theme.options.y` keeping the last option on screen (its centre
+/// at most half a line above the bottom edge).
+pub const MAX_OPTIONS_Y: u32 = 100 - OPTION_SPACING / 2 - OPTION_SPACING * (CHOICES as u32 - 1);
+
+/// Options of one clip in multiple-choice mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuizChoices {
+    /// Option texts, in display order
+    pub options: Vec<String>,
+    /// Index of the answer in `options`
+    pub correct: usize,
+}
+
+impl QuizChoices {
+    /// Option line as displayed, e.g. "B. Queen".
+    pub fn line(&self, i: usize) -> String {
+        format!("{}. {}", option_label(i), self.options[i])
+    }
+}
+
+/// Option letter: 'A', 'B', ...
+pub fn option_label(i: usize) -> char {
+    (b'A' + i as u8) as char
+}
+
+/// Options of every clip, in playing order (see [`Project::all_clips`]).
+///
+/// Every entry is `None` unless the project has a `quiz` section. The draw
+/// only depends on `quiz.seed`, the clip position and the answers, so
+/// rendering the same project twice shows the same options.
+pub fn quiz_choices(p: &Project) -> Result<Vec<Option<QuizChoices>>> {
+    let Some(quiz) = p.quiz.as_ref() else {
+        return Ok(p.all_clips().map(|_| None).collect());
+    };
+
+    p.clip_paths()
+        .into_iter()
+        .enumerate()
+        .map(|(i, (at, clip))| {
+            let answer = clip.answer.summary();
+            let mut candidates = distractor_candidates(p, clip);
+            if candidates.len() < CHOICES - 1 {
+                bail!(
+                    "{at} needs {} distractors, only {} available",
+                    CHOICES - 1,
+                    candidates.len()
+                );
+            }
+
+            let mut rng = seeded_rng(quiz.seed.wrapping_add(i as u64));
+            // Explicit distractors come first; only the pool is drawn from
+            let explicit = clean(&clip.distractors, &answer).len();
+            if explicit >= CHOICES - 1 {
+                candidates.truncate(explicit);
+                shuffle(&mut candidates, &mut rng);
+            } else {
+                shuffle(&mut candidates[explicit..], &mut rng);
+            }
+
+            let mut options: Vec<String> = candidates[..CHOICES - 1].to_vec();
+            options.push(answer.clone());
+            shuffle(&mut options, &mut rng);
+            let correct = options
+                .iter()
+                .position(|o| *o == answer)
+                .unwrap_or_default();
+            Ok(Some(QuizChoices { options, correct }))
+        })
+        .collect()
+}
+
+/// Possible wrong options of `clip`: its explicit distractors, then the
+/// answers of the other clips (trimmed, without duplicates nor the answer).
+pub fn distractor_candidates(p: &Project, clip: &Clip) -> Vec<String> {
+    let answer = clip.answer.summary();
+    let mut candidates = clean(&clip.distractors, &answer);
+    for other in p.all_clips() {
+        let other = other.answer.summary();
+        if !other.is_empty() && !contains(&candidates, &other) && !same(&other, &answer) {
+            candidates.push(other);
+        }
+    }
+    candidates
+}
+
+/// Explicit distractors, trimmed, without blanks, duplicates nor the answer.
+fn clean(distractors: &[String], answer: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for d in distractors.iter().map(|d| d.trim()) {
+        if !d.is_empty() && !contains(&out, d) && !same(d, answer) {
+            out.push(d.to_string());
+        }
+    }
+    out
+}
+
+fn contains(list: &[String], s: &str) -> bool {
+    list.iter().any(|l| same(l, s))
+}
+
+/// Options are compared case-insensitively ("queen" is not a valid distractor for "Queen").
+fn same(a: &str, b: &str) -> bool {
+    a.trim().to_lowercase() == b.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fixtures::{clip, sample_project};
+    use crate::model::Quiz;
+
+    fn project() -> Project {
+        let mut p = sample_project();
+        p.quiz = Some(Quiz { seed: 7 });
+        p.clips = vec![
+            clip("videos/a.mp4", "0s", "Queen"),
+            clip("videos/b.mp4", "0s", "ABBA"),
+            clip("videos/c.mp4", "0s", "queen "),
+            clip("videos/d.mp4", "0s", "Blondie"),
+            Clip {
+                distractors: ["Tata", "toto", "Titi", "Tata", "Tutu", "Tete"]
+                    .map(String::from)
+                    .to_vec(),
+                ..clip("videos/e.mp4", "0s", "Toto")
+            },
+        ];
+        p
+    }
+
+    #[test]
+    fn draws_the_answer_and_three_distractors() {
+        let p = project();
+        let all = quiz_choices(&p).unwrap();
+        assert_eq!(all.len(), 5);
+
+        for (clip, choices) in p.all_clips().zip(&all) {
+            let choices = choices.as_ref().unwrap();
+            assert_eq!(choices.options.len(), CHOICES);
+            assert_eq!(choices.options[choices.correct], clip.answer.summary());
+            let answers = choices
+                .options
+                .iter()
+                .filter(|o| same(o, &clip.answer.summary()))
+                .count();
+            assert_eq!(answers, 1, "{choices:?}");
+        }
+
+        // Explicit distractors win over the other answers
+        let toto = all[4].as_ref().unwrap();
+        assert!(
+            toto.options
+                .iter()
+                .all(|o| ["Toto", "Tata", "Titi", "Tutu", "Tete"].contains(&o.as_str())),
+            "{toto:?}"
+        );
+        assert_eq!(
+            toto.line(toto.correct),
+            format!("{}. Toto", option_label(toto.correct))
+        );
+    }
+
+    #[test]
+    fn same_seed_same_options() {
+        let p = project();
+        assert_eq!(quiz_choices(&p).unwrap(), quiz_choices(&p).unwrap());
+
+        // Pinned draw: the same seed must render the same options on every build
+        let toto = quiz_choices(&p).unwrap().remove(4).unwrap();
+        assert_eq!(
+            toto,
+            QuizChoices {
+                options: vec!["Tete".into(), "Titi".into(), "Toto".into(), "Tutu".into()],
+                correct: 2
+            }
+        );
+
+        let mut other = project();
+        other.quiz.as_mut().unwrap().seed = 8;
+        assert_ne!(quiz_choices(&p).unwrap(), quiz_choices(&other).unwrap());
+    }
+
+    #[test]
+    fn fills_missing_distractors_from_other_answers() {
+        let p = project();
+        let queen = &p.clips[0];
+        assert_eq!(
+            distractor_candidates(&p, queen),
+            ["ABBA", "Blondie", "Toto"]
+        );
+
+        let mut short = project();
+        short.clips.truncate(3);
+        let err = quiz_choices(&short).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "clips[0] needs 3 distractors, only 1 available"
+        );
+    }
+
+    #[test]
+    fn disabled_without_quiz_section() {
+        let mut p = project();
+        p.quiz = None;
+        assert!(quiz_choices(&p).unwrap().iter().all(Option::is_none));
+    }
+}
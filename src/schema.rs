@@ -0,0 +1,63 @@
+// src/schema.rs
+
+use schemars::Schema;
+
+use crate::model::Project;
+
+/// JSON Schema of a project file, generated from the model types.
+pub fn project_schema() -> Schema {
+    schemars::schema_for!(Project)
+}
+
+/// [`project_schema`] as pretty-printed JSON, ready to be written to a file.
+pub fn project_schema_json() -> String {
+    let mut json = serde_json::to_string_pretty(&project_schema())
+        .expect("a JSON Schema is always serializable");
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const COMMITTED: &str = include_str!("../schema/project.schema.json");
+
+    /// The published schema must follow the model.
+    /// Run with `BLINDTEST_UPDATE_SCHEMA=1` to regenerate it.
+    #[test]
+    fn committed_schema_is_up_to_date() {
+        let generated = project_schema_json();
+        if std::env::var_os("BLINDTEST_UPDATE_SCHEMA").is_some() {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/project.schema.json");
+            std::fs::write(path, &generated).unwrap();
+            return;
+        }
+        assert!(
+            generated == COMMITTED,
+            "schema/project.schema.json is outdated: \
+             run `BLINDTEST_UPDATE_SCHEMA=1 cargo test committed_schema` to regenerate it"
+        );
+    }
+
+    #[test]
+    fn describes_timecodes_and_resolution() {
+        let schema: Value = serde_json::from_str(&project_schema_json()).unwrap();
+        let defs = &schema["$defs"];
+
+        assert_eq!(defs["Timecode"]["type"], "string");
+        assert!(defs["Timecode"]["pattern"].is_string());
+        assert_eq!(
+            defs["Output"]["properties"]["resolution"]["pattern"],
+            crate::model::RESOLUTION_PATTERN
+        );
+        assert_eq!(defs["Clip"]["additionalProperties"], false);
+        assert!(
+            schema["required"]
+                .as_array()
+                .unwrap()
+                .contains(&"output".into())
+        );
+    }
+}
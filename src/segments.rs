@@ -0,0 +1,751 @@
+// src/segments.rs
+
+use crate::ffmpeg::{self, Progress};
+use crate::ffmpeg_command::{
+    Canvas, CommandSpec, clip_filters, guess_image_filter, guess_image_labels, image_card_filters,
+    ms_to_seconds_f64, push_clip_input, push_encoding_args, push_image, push_looped_image,
+    resolve_clip_timings, round_card_filters, round_title_ms,
+};
+use crate::model::{Clip, Project};
+use crate::quiz::{QuizChoices, quiz_choices};
+use crate::theme::{Look, resolve_look};
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Instant;
+
+/// Bumped whenever the segment pipeline changes, so old cache entries are not reused.
+const CACHE_VERSION: &str = "blindtest-segment-v1";
+
+/// Audio sample rate of every segment (the concat demuxer needs identical streams).
+const SEGMENT_AUDIO_RATE: &str = "44100";
+
+/// One intermediate file of a segmented render (intro, round card, clip or outro).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// Human readable name (e.g. `intro`, `clips[3]`, `rounds[1]`)
+    pub name: String,
+    /// Cache key, derived from the ffmpeg arguments and the input files
+    pub key: String,
+    /// Cached segment file (`<cache_dir>/<key>.mp4`)
+    pub path: PathBuf,
+    /// Length of the segment in the output video
+    pub duration_ms: u64,
+    /// ffmpeg command building the segment (writes a `.part.mp4` first)
+    pub spec: CommandSpec,
+}
+
+impl Segment {
+    /// Temporary file written by `spec`, renamed to `path` once complete.
+    pub fn part_path(&self) -> PathBuf {
+        self.path.with_extension("part.mp4")
+    }
+
+    /// True if the segment is already in the cache.
+    pub fn is_cached(&self) -> bool {
+        self.path.is_file()
+    }
+}
+
+/// Everything needed for a segmented render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentPlan {
+    /// Segments in playing order
+    pub segments: Vec<Segment>,
+    /// Concat demuxer list (`file '<key>.mp4'` lines)
+    pub list_path: PathBuf,
+    /// Final ffmpeg command joining segments without re-encoding
+    pub concat: CommandSpec,
+}
+
+/// Default cache directory: `.blindtest-cache` next to the output file.
+pub fn default_cache_dir(p: &Project) -> PathBuf {
+    Path::new(p.output.path.trim())
+        .parent()
+        .unwrap_or(Path::new(""))
+        .join(".blindtest-cache")
+}
+
+/// Split the project into one ffmpeg command per segment.
+///
+/// Segments follow the final video order: intro, top-level clips,
+/// (round title card + round clips)..., outro. Each one is encoded with the
+/// same parameters so they can be joined with the concat demuxer (`-c copy`).
+pub fn plan_segments(p: &Project, cache_dir: &Path) -> Result<SegmentPlan> {
+    let canvas = Canvas::from_project(p)?;
+    let choices = quiz_choices(p)?;
+    let look = resolve_look(p)?;
+    let mut segments: Vec<Segment> = Vec::new();
+
+    if let Some(intro) = p.intro.as_ref() {
+        let dur_ms = intro.duration.as_ms(canvas.fps);
+        let dur_s = ms_to_seconds_f64(dur_ms);
+        let mut args: Vec<String> = vec!["-y".into()];
+        push_looped_image(&mut args, &intro.background);
+        args.push("-i".into());
+        args.push(intro.music.trim().to_string());
+        let filters = image_card_filters(canvas, &look, 0, 1, &intro.title, dur_s, "intro");
+        segments.push(make_segment(
+            "intro".into(),
+            args,
+            filters,
+            "intro",
+            dur_ms,
+            cache_dir,
+        ));
+    }
+
+    let mut clips = p.clip_paths().into_iter().zip(&choices);
+    for ((at, c), choices) in clips.by_ref().take(p.clips.len()) {
+        segments.push(clip_segment(
+            p,
+            canvas,
+            &look,
+            &at,
+            c,
+            choices.as_ref(),
+            cache_dir,
+        )?);
+    }
+
+    for (r, round) in p.rounds.iter().enumerate() {
+        let dur_ms = round_title_ms(round, canvas.fps);
+        let dur_s = ms_to_seconds_f64(dur_ms);
+        let mut args: Vec<String> = vec!["-y".into()];
+        let bg = round.background.as_deref().map(|bg| {
+            push_looped_image(&mut args, bg);
+            0
+        });
+        let filters = round_card_filters(canvas, &look, bg, &round.title, dur_s, "round");
+        segments.push(make_segment(
+            format!("rounds[{r}]"),
+            args,
+            filters,
+            "round",
+            dur_ms,
+            cache_dir,
+        ));
+
+        for ((at, c), choices) in clips.by_ref().take(round.clips.len()) {
+            segments.push(clip_segment(
+                p,
+                canvas,
+                &look,
+                &at,
+                c,
+                choices.as_ref(),
+                cache_dir,
+            )?);
+        }
+    }
+
+    if let Some(outro) = p.outro.as_ref() {
+        let dur_ms = outro.duration.as_ms(canvas.fps);
+        let dur_s = ms_to_seconds_f64(dur_ms);
+        let mut args: Vec<String> = vec!["-y".into()];
+        push_looped_image(&mut args, &outro.background);
+        args.push("-i".into());
+        args.push(outro.music.trim().to_string());
+        let filters = image_card_filters(canvas, &look, 0, 1, &outro.text, dur_s, "outro");
+        segments.push(make_segment(
+            "outro".into(),
+            args,
+            filters,
+            "outro",
+            dur_ms,
+            cache_dir,
+        ));
+    }
+
+    // The list itself is content-addressed too: same segments => same file
+    let list_key = hash_strings(segments.iter().map(|s| s.key.as_str()));
+    let list_path = cache_dir.join(format!("concat-{list_key}.txt"));
+
+    let concat = CommandSpec {
+        program: "ffmpeg".into(),
+        args: vec![
+            "-y".into(),
+            "-f".into(),
+            "concat".into(),
+            "-i".into(),
+            list_path.to_string_lossy().to_string(),
+            "-c".into(),
+            "copy".into(),
+            p.output.path.trim().to_string(),
+        ],
+    };
+
+    Ok(SegmentPlan {
+        segments,
+        list_path,
+        concat,
+    })
+}
+
+/// Content of the concat demuxer list.
+///
+/// Entries are file names only: the demuxer resolves them relative to the
+/// list file, which lives in the cache directory next to the segments.
+pub fn concat_list(plan: &SegmentPlan) -> String {
+    let mut s = String::new();
+    for seg in &plan.segments {
+        let name = seg
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        s.push_str(&format!("file '{name}'\n"));
+    }
+    s
+}
+
+/// Segments that are not in the cache yet.
+pub fn missing_segments(plan: &SegmentPlan) -> Vec<&Segment> {
+    plan.segments.iter().filter(|s| !s.is_cached()).collect()
+}
+
+/// Build one segment, then move it into the cache.
+///
+/// The segment is written to a `.part.mp4` file first so an interrupted
+/// render never leaves a truncated file under a valid cache key.
+pub fn render_segment(seg: &Segment) -> Result<()> {
+    ffmpeg::run(&seg.spec).with_context(|| format!("failed to render segment {}", seg.name))?;
+    fs::rename(seg.part_path(), &seg.path)
+        .with_context(|| format!("failed to store segment {}", seg.path.display()))?;
+    Ok(())
+}
+
+/// Render the missing segments with up to `jobs` concurrent ffmpeg processes,
+/// then join all of them into the output file.
+///
+/// The result does not depend on `jobs`: every segment is an independent,
+/// deterministic command and the concat list always follows the plan order.
+///
+/// `on_progress` is called once per finished segment (from the worker
+/// threads) and once more when the output is complete; cached segments count
+/// as already done.
+///
+/// Returns the number of segments that were (re)built.
+pub fn render_segments<F>(plan: &SegmentPlan, jobs: usize, on_progress: F) -> Result<usize>
+where
+    F: Fn(&Progress) + Sync,
+{
+    if let Some(parent) = plan.list_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create cache dir {}", parent.display()))?;
+    }
+
+    let missing = missing_segments(plan);
+    let progress = SegmentProgress::new(plan);
+    run_pool(&missing, jobs, |seg| {
+        render_segment(seg)?;
+        on_progress(&progress.finish(seg));
+        Ok(())
+    })?;
+
+    fs::write(&plan.list_path, concat_list(plan))
+        .with_context(|| format!("failed to write {}", plan.list_path.display()))?;
+    ffmpeg::run(&plan.concat)?;
+    on_progress(&progress.end());
+
+    Ok(missing.len())
+}
+
+/// Progress of a segmented render, measured in output time.
+struct SegmentProgress {
+    /// Duration of the whole output (all segments)
+    total_ms: u64,
+    /// Duration of the segments found in the cache
+    cached_ms: u64,
+    /// Duration of the segments rendered so far
+    rendered_ms: Mutex<u64>,
+    started: Instant,
+}
+
+impl SegmentProgress {
+    fn new(plan: &SegmentPlan) -> Self {
+        let total_ms = plan.segments.iter().map(|s| s.duration_ms).sum();
+        let cached_ms = plan
+            .segments
+            .iter()
+            .filter(|s| s.is_cached())
+            .map(|s| s.duration_ms)
+            .sum();
+        Self {
+            total_ms,
+            cached_ms,
+            rendered_ms: Mutex::new(0),
+            started: Instant::now(),
+        }
+    }
+
+    /// Record a rendered segment and return the updated progress.
+    fn finish(&self, seg: &Segment) -> Progress {
+        let mut rendered_ms = self.rendered_ms.lock().unwrap();
+        *rendered_ms += seg.duration_ms;
+        self.report(*rendered_ms, false)
+    }
+
+    /// Final report, once the segments are joined.
+    fn end(&self) -> Progress {
+        let rendered_ms = *self.rendered_ms.lock().unwrap();
+        self.report(rendered_ms, true)
+    }
+
+    fn report(&self, rendered_ms: u64, done: bool) -> Progress {
+        let elapsed = self.started.elapsed();
+        let secs = elapsed.as_secs_f64();
+        Progress {
+            out_time_ms: self.cached_ms + rendered_ms,
+            total_ms: self.total_ms,
+            skipped_ms: self.cached_ms,
+            speed: (secs > 0.0).then(|| ms_to_seconds_f64(rendered_ms) / secs),
+            elapsed,
+            done,
+        }
+    }
+}
+
+/// Run `task` on every item with up to `jobs` worker threads.
+///
+/// Workers pick the next item in order; once a task fails no new item is
+/// started. On failure, the error of the first failing item (in item order,
+/// not completion order) is returned so errors are reproducible.
+fn run_pool<T, F>(items: &[T], jobs: usize, task: F) -> Result<()>
+where
+    T: Sync,
+    F: Fn(&T) -> Result<()> + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let errors: Mutex<Vec<(usize, anyhow::Error)>> = Mutex::new(Vec::new());
+    let workers = jobs.clamp(1, items.len().max(1));
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while !failed.load(Ordering::SeqCst) {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    if let Err(e) = task(item) {
+                        failed.store(true, Ordering::SeqCst);
+                        errors.lock().unwrap().push((i, e));
+                    }
+                }
+            });
+        }
+    });
+
+    let mut errors = errors.into_inner().unwrap();
+    errors.sort_by_key(|(i, _)| *i);
+    match errors.into_iter().next() {
+        Some((_, e)) => Err(e),
+        None => Ok(()),
+    }
+}
+
+fn clip_segment(
+    p: &Project,
+    canvas: Canvas,
+    look: &Look,
+    at: &str,
+    c: &Clip,
+    choices: Option<&QuizChoices>,
+    cache_dir: &Path,
+) -> Result<Segment> {
+    let timings = resolve_clip_timings(c, &p.timings, p.fps())
+        .with_context(|| format!("{at} has invalid timings"))?;
+    let mut args: Vec<String> = vec!["-y".into()];
+    push_clip_input(&mut args, c, timings, p.fps());
+    let mut filters: Vec<String> = Vec::new();
+    if let Some(image) = look.guess_image.as_deref() {
+        push_image(&mut args, image);
+        filters.push(guess_image_filter(
+            canvas,
+            1,
+            &guess_image_labels(0, timings),
+        ));
+    }
+    filters.extend(clip_filters(canvas, look, 0, 0, c, timings, choices));
+    Ok(make_segment(
+        at.to_string(),
+        args,
+        filters,
+        "0",
+        timings.total_ms(),
+        cache_dir,
+    ))
+}
+
+/// Finish a segment command: filter graph, maps, encoding and cache key.
+///
+/// `label` is the suffix of the `[v{label}][a{label}]` outputs of `filters`.
+fn make_segment(
+    name: String,
+    mut args: Vec<String>,
+    filters: Vec<String>,
+    label: &str,
+    duration_ms: u64,
+    cache_dir: &Path,
+) -> Segment {
+    args.push("-filter_complex".into());
+    args.push(filters.join(";"));
+    args.push("-map".into());
+    args.push(format!("[v{label}]"));
+    args.push("-map".into());
+    args.push(format!("[a{label}]"));
+    push_encoding_args(&mut args);
+    args.push("-ar".into());
+    args.push(SEGMENT_AUDIO_RATE.into());
+    args.push("-ac".into());
+    args.push("2".into());
+
+    let key = segment_key(&args);
+    let path = cache_dir.join(format!("{key}.mp4"));
+
+    let mut seg = Segment {
+        name,
+        key,
+        path,
+        duration_ms,
+        spec: CommandSpec {
+            program: "ffmpeg".into(),
+            args,
+        },
+    };
+    let part = seg.part_path();
+    seg.spec.args.push(part.to_string_lossy().to_string());
+    seg
+}
+
+/// Cache key of a segment: its ffmpeg arguments (which carry the timings,
+/// texts and output settings) plus the size and mtime of every input file
+/// and of every theme font.
+fn segment_key(args: &[String]) -> String {
+    let mut parts: Vec<String> = vec![CACHE_VERSION.into()];
+    parts.extend(args.iter().cloned());
+
+    for (i, arg) in args.iter().enumerate() {
+        if let Some(next) = args.get(i + 1) {
+            match arg.as_str() {
+                "-i" => parts.push(file_fingerprint(Path::new(next))),
+                "-filter_complex" => parts.extend(
+                    font_files(next)
+                        .iter()
+                        .map(|f| file_fingerprint(Path::new(f))),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    hash_strings(parts.iter().map(String::as_str))
+}
+
+/// Font files of the `drawtext` filters (`fontfile='...'`, escaped like texts).
+fn font_files(filter_complex: &str) -> Vec<String> {
+    let mut fonts: Vec<String> = Vec::new();
+    for rest in filter_complex.split("fontfile='").skip(1) {
+        let mut font = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => font.extend(chars.next()),
+                '\'' => break,
+                c => font.push(c),
+            }
+        }
+        if !fonts.contains(&font) {
+            fonts.push(font);
+        }
+    }
+    fonts
+}
+
+fn file_fingerprint(path: &Path) -> String {
+    match fs::metadata(path) {
+        Ok(meta) => {
+            let mtime = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_nanos())
+                .unwrap_or(0);
+            format!("{}:{}:{mtime}", path.display(), meta.len())
+        }
+        Err(_) => format!("{}:missing", path.display()),
+    }
+}
+
+fn hash_strings<'a>(items: impl Iterator<Item = &'a str>) -> String {
+    let mut hasher = Sha256::new();
+    for item in items {
+        hasher.update(item.as_bytes());
+        hasher.update([0u8]);
+    }
+    hasher.finalize()[..16]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{clip, intro, round, sample_project};
+    use crate::model::{Theme, ThemeRef};
+
+    fn project() -> Project {
+        let mut p = sample_project();
+        p.intro = Some(intro("Blind Test", "00:00:03.000"));
+        p.clips.push(clip(
+            "videos/b.mp4",
+            "00:00:02.000",
+            "Daft Punk - One More Time",
+        ));
+        p.rounds = vec![round(
+            "Films",
+            vec![clip("videos/c.mp4", "00:00:03.000", "Star Wars")],
+        )];
+        p
+    }
+
+    #[test]
+    fn plans_segments_in_video_order() {
+        let plan = plan_segments(&project(), Path::new("cache")).unwrap();
+        let names: Vec<&str> = plan.segments.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "intro",
+                "clips[0]",
+                "clips[1]",
+                "rounds[0]",
+                "rounds[0].clips[0]"
+            ]
+        );
+
+        let clip = &plan.segments[1];
+        let joined = clip.spec.args.join(" ");
+        assert!(
+            joined.starts_with("-y -ss 1.000 -t 15.000 -i videos/a.mp4 -filter_complex"),
+            "args were:\n{joined}"
+        );
+        assert!(
+            joined.contains("-map [v0] -map [a0]"),
+            "args were:\n{joined}"
+        );
+        assert_eq!(
+            clip.path,
+            Path::new("cache").join(format!("{}.mp4", clip.key))
+        );
+        assert_eq!(
+            clip.spec.args.last().unwrap(),
+            &Path::new("cache")
+                .join(format!("{}.part.mp4", clip.key))
+                .to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn keys_only_change_for_edited_segments() {
+        let before = plan_segments(&project(), Path::new("cache")).unwrap();
+        let again = plan_segments(&project(), Path::new("cache")).unwrap();
+        assert_eq!(before, again);
+
+        let mut p = project();
+        p.clips[1].answer = "Daft Punk - Around the World".into();
+        let after = plan_segments(&p, Path::new("cache")).unwrap();
+
+        let changed: Vec<&str> = before
+            .segments
+            .iter()
+            .zip(&after.segments)
+            .filter(|(a, b)| a.key != b.key)
+            .map(|(_, b)| b.name.as_str())
+            .collect();
+        assert_eq!(changed, ["clips[1]"]);
+        assert_ne!(before.list_path, after.list_path);
+    }
+
+    #[test]
+    fn output_settings_are_part_of_the_key() {
+        let before = plan_segments(&project(), Path::new("cache")).unwrap();
+
+        let mut p = project();
+        p.output.resolution = Some("1920x1080".into());
+        let after = plan_segments(&p, Path::new("cache")).unwrap();
+
+        assert!(
+            before
+                .segments
+                .iter()
+                .zip(&after.segments)
+                .all(|(a, b)| a.key != b.key)
+        );
+    }
+
+    #[test]
+    fn theme_fonts_and_image_are_part_of_the_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let font = dir.path().join("it's.ttf");
+        fs::write(&font, b"v1").unwrap();
+
+        let mut p = project();
+        p.theme = Some(ThemeRef::Inline(Box::new(Theme {
+            font: Some(font.to_string_lossy().into_owned()),
+            guess_image: Some("guess.png".into()),
+            ..Default::default()
+        })));
+        let before = plan_segments(&p, Path::new("cache")).unwrap();
+
+        // Clip segments decode the guess image once (single frame, no -loop)
+        let clip = &before.segments[1];
+        assert_eq!(clip.name, "clips[0]");
+        assert!(
+            clip.spec
+                .args
+                .windows(3)
+                .any(|w| w == ["-i", "guess.png", "-filter_complex"]),
+            "{:?}",
+            clip.spec.args
+        );
+        assert!(
+            clip.spec
+                .args
+                .iter()
+                .any(|a| a.starts_with("[1:v]scale=1280:720,setsar=1,split=1[v0gimg];"))
+        );
+        assert_eq!(
+            font_files(&before.segments[0].spec.args.join(" ")),
+            [font.to_string_lossy()]
+        );
+
+        fs::write(&font, b"v2 (bigger)").unwrap();
+        let after = plan_segments(&p, Path::new("cache")).unwrap();
+        assert!(
+            before
+                .segments
+                .iter()
+                .zip(&after.segments)
+                .all(|(a, b)| a.key != b.key)
+        );
+    }
+
+    #[test]
+    fn joins_segments_with_concat_demuxer() {
+        let plan = plan_segments(&project(), Path::new("cache")).unwrap();
+
+        let list = concat_list(&plan);
+        assert_eq!(list.lines().count(), 5);
+        assert_eq!(
+            list.lines().next().unwrap(),
+            format!("file '{}.mp4'", plan.segments[0].key)
+        );
+
+        let joined = plan.concat.args.join(" ");
+        assert_eq!(
+            joined,
+            format!(
+                "-y -f concat -i {} -c copy render/out.mp4",
+                plan.list_path.to_string_lossy()
+            )
+        );
+    }
+
+    #[test]
+    fn cached_segments_are_not_rebuilt() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = plan_segments(&project(), dir.path()).unwrap();
+        assert_eq!(missing_segments(&plan).len(), 5);
+
+        fs::write(&plan.segments[2].path, b"cached").unwrap();
+        let missing: Vec<&str> = missing_segments(&plan)
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(
+            missing,
+            ["intro", "clips[0]", "rounds[0]", "rounds[0].clips[0]"]
+        );
+    }
+
+    #[test]
+    fn segment_durations_add_up_to_the_video() {
+        let p = project();
+        let plan = plan_segments(&p, Path::new("cache")).unwrap();
+        let durations: Vec<u64> = plan.segments.iter().map(|s| s.duration_ms).collect();
+        assert_eq!(durations, [3_000, 15_000, 15_000, 3_000, 15_000]);
+        assert_eq!(
+            durations.iter().sum::<u64>(),
+            crate::ffmpeg_command::expected_duration_ms(&p).unwrap()
+        );
+    }
+
+    #[test]
+    fn progress_counts_cached_segments_as_done() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = plan_segments(&project(), dir.path()).unwrap();
+        fs::write(&plan.segments[1].path, b"cached").unwrap();
+
+        let progress = SegmentProgress::new(&plan);
+        let p = progress.finish(&plan.segments[0]);
+        assert_eq!(p.out_time_ms, 18_000);
+        assert_eq!(p.total_ms, 51_000);
+        assert_eq!(p.skipped_ms, 15_000);
+        assert!(!p.done);
+
+        let p = progress.finish(&plan.segments[2]);
+        assert_eq!(p.out_time_ms, 33_000);
+
+        let p = progress.end();
+        assert!(p.done);
+        assert_eq!(p.fraction(), 1.0);
+    }
+
+    #[test]
+    fn pool_runs_every_item_once() {
+        let items: Vec<usize> = (0..20).collect();
+        for jobs in [1, 4, 64] {
+            let seen = Mutex::new(Vec::new());
+            run_pool(&items, jobs, |i| {
+                seen.lock().unwrap().push(*i);
+                Ok(())
+            })
+            .unwrap();
+
+            let mut seen = seen.into_inner().unwrap();
+            seen.sort();
+            assert_eq!(seen, items, "jobs = {jobs}");
+        }
+    }
+
+    #[test]
+    fn pool_reports_first_failing_item() {
+        let items: Vec<usize> = (0..10).collect();
+        for jobs in [1, 3, 10] {
+            let err = run_pool(&items, jobs, |i| {
+                if *i >= 4 {
+                    anyhow::bail!("item {i} failed");
+                }
+                Ok(())
+            })
+            .unwrap_err();
+            assert_eq!(err.to_string(), "item 4 failed", "jobs = {jobs}");
+        }
+    }
+
+    #[test]
+    fn pool_accepts_empty_input() {
+        let items: Vec<usize> = Vec::new();
+        run_pool(&items, 8, |_| Ok(())).unwrap();
+    }
+}
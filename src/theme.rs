@@ -0,0 +1,315 @@
+// src/theme.rs
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::Path;
+
+use crate::format::ProjectFormat;
+use crate::model::{Project, TextStyle, Theme, ThemeRef};
+
+/// Names accepted by `theme` and `theme.extends`.
+pub const BUILTIN_THEMES: [&str; 3] = ["classic", "neon", "minimal"];
+
+/// Built-in theme by name.
+pub fn builtin_theme(name: &str) -> Option<Theme> {
+    let style = |color: &str, box_color: Option<&str>| TextStyle {
+        color: Some(color.into()),
+        box_color: box_color.map(Into::into),
+        ..Default::default()
+    };
+
+    match name {
+        // The original look: white outlined text on black screens
+        "classic" => Some(Theme::default()),
+        "neon" => Some(Theme {
+            background: Some("#0d0221".into()),
+            text_color: Some("#ff2a6d".into()),
+            border_color: Some("#05d9e8".into()),
+            border_width: Some(2),
+            answer: Some(TextStyle {
+                border_color: Some("#ff2a6d".into()),
+                ..style("#05d9e8", None)
+            }),
+            highlight: Some("#ff2a6d@0.7".into()),
+            ..Default::default()
+        }),
+        // Dark text on a light screen, boxed text over images and videos
+        "minimal" => Some(Theme {
+            background: Some("#f5f5f0".into()),
+            text_color: Some("#222222".into()),
+            border_width: Some(0),
+            title: Some(style("#222222", Some("white@0.7"))),
+            answer: Some(style("white", Some("black@0.6"))),
+            highlight: Some("#2e7d32@0.9".into()),
+            ..Default::default()
+        }),
+        _ => None,
+    }
+}
+
+/// A theme name is a file when it has a project file extension.
+pub fn is_theme_file(name: &str) -> bool {
+    let ext = Path::new(name.trim())
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase());
+    matches!(ext.as_deref(), Some("json" | "yaml" | "yml" | "toml"))
+}
+
+/// Read a theme file (JSON, YAML or TOML by extension).
+///
+/// Its font and image paths are relative to the theme file.
+pub fn load_theme_file(path: &Path) -> Result<Theme> {
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("failed to read theme file: {}", path.display()))?;
+    let format = ProjectFormat::from_path(path);
+    let mut theme: Theme = format.parse(&source).with_context(|| {
+        format!(
+            "invalid {} in theme file: {}",
+            format.name(),
+            path.display()
+        )
+    })?;
+    theme.resolve_paths(path.parent().unwrap_or(Path::new("")));
+    Ok(theme)
+}
+
+/// The theme of a project, as written (built-in, file or inline).
+pub fn project_theme(p: &Project) -> Result<Theme> {
+    match &p.theme {
+        None => Ok(Theme::default()),
+        Some(ThemeRef::Inline(theme)) => Ok(theme.as_ref().clone()),
+        Some(ThemeRef::Name(name)) => {
+            let name = name.trim();
+            if let Some(theme) = builtin_theme(name) {
+                Ok(theme)
+            } else if is_theme_file(name) {
+                load_theme_file(Path::new(name))
+            } else {
+                bail!("{}", unknown_theme(name))
+            }
+        }
+    }
+}
+
+/// Error message for a theme name that is neither built-in nor a file.
+pub fn unknown_theme(name: &str) -> String {
+    format!(
+        "unknown theme '{name}' (built-in themes: {}; or a .json, .yaml or .toml theme file)",
+        BUILTIN_THEMES.join(", ")
+    )
+}
+
+/// Resolved look of one kind of text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextLook {
+    pub font: Option<String>,
+    pub size: u32,
+    pub color: String,
+    /// `None` keeps the ffmpeg default (black)
+    pub border_color: Option<String>,
+    pub border_width: u32,
+    pub box_color: Option<String>,
+    /// Vertical centre in percent of the height; `None` keeps the default layout
+    pub y: Option<u32>,
+}
+
+impl TextLook {
+    fn new(size: u32, border_width: u32) -> Self {
+        Self {
+            font: None,
+            size,
+            color: "white".into(),
+            border_color: None,
+            border_width,
+            box_color: None,
+            y: None,
+        }
+    }
+
+    fn apply(&mut self, style: &TextStyle) {
+        if let Some(size) = style.size {
+            self.size = size;
+        }
+        if let Some(color) = &style.color {
+            self.color = color.trim().to_string();
+        }
+        if let Some(color) = &style.border_color {
+            self.border_color = Some(color.trim().to_string());
+        }
+        if let Some(width) = style.border_width {
+            self.border_width = width;
+        }
+        if let Some(color) = &style.box_color {
+            self.box_color = Some(color.trim().to_string());
+        }
+        if let Some(y) = style.y {
+            self.y = Some(y);
+        }
+    }
+}
+
+/// Every visual setting of the rendered video, once the theme is applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Look {
+    /// Colour of the guess/hint screens and of round cards without background
+    pub background: String,
+    pub guess_image: Option<String>,
+    pub title: TextLook,
+    pub countdown: TextLook,
+    pub options: TextLook,
+    pub hint: TextLook,
+    pub answer: TextLook,
+    /// Box colour of the correct quiz option at reveal
+    pub highlight: String,
+}
+
+impl Default for Look {
+    /// The "classic" theme.
+    fn default() -> Self {
+        Self {
+            background: "black".into(),
+            guess_image: None,
+            title: TextLook::new(72, 4),
+            countdown: TextLook::new(96, 4),
+            options: TextLook::new(48, 3),
+            hint: TextLook::new(72, 4),
+            answer: TextLook::new(48, 3),
+            highlight: "green@0.8".into(),
+        }
+    }
+}
+
+impl Look {
+    /// Apply `theme` over this look: its base theme first, then its own fields.
+    /// Global text settings come before the per-text styles of the same theme.
+    pub fn apply(&mut self, theme: &Theme) -> Result<()> {
+        if let Some(base) = theme.extends.as_deref() {
+            let base = builtin_theme(base.trim())
+                .with_context(|| format!("unknown built-in theme '{}'", base.trim()))?;
+            self.apply(&base)?;
+        }
+
+        if let Some(color) = &theme.background {
+            self.background = color.trim().to_string();
+        }
+        if let Some(image) = &theme.guess_image {
+            self.guess_image = Some(image.trim().to_string());
+        }
+        if let Some(color) = &theme.highlight {
+            self.highlight = color.trim().to_string();
+        }
+
+        let global = TextStyle {
+            color: theme.text_color.clone(),
+            border_color: theme.border_color.clone(),
+            border_width: theme.border_width,
+            ..Default::default()
+        };
+        for (text, style) in [
+            (&mut self.title, &theme.title),
+            (&mut self.countdown, &theme.countdown),
+            (&mut self.options, &theme.options),
+            (&mut self.hint, &theme.hint),
+            (&mut self.answer, &theme.answer),
+        ] {
+            text.apply(&global);
+            if let Some(style) = style {
+                text.apply(style);
+            }
+            if let Some(font) = &theme.font {
+                text.font = Some(font.trim().to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Look of a project: the classic look with its theme applied.
+pub fn resolve_look(p: &Project) -> Result<Look> {
+    let mut look = Look::default();
+    look.apply(&project_theme(p)?)?;
+    Ok(look)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::sample_project;
+
+    /// Sample project with `theme` written as JSON.
+    fn project(theme: &str) -> Project {
+        let mut p = sample_project();
+        p.theme = Some(serde_json::from_str(theme).unwrap());
+        p
+    }
+
+    #[test]
+    fn classic_is_the_default_look() {
+        let mut p = project(r#""classic""#);
+        assert_eq!(resolve_look(&p).unwrap(), Look::default());
+        p.theme = None;
+        assert_eq!(resolve_look(&p).unwrap(), Look::default());
+
+        for name in BUILTIN_THEMES {
+            assert!(builtin_theme(name).is_some(), "{name}");
+        }
+        let err = resolve_look(&project(r#""neo""#)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown theme 'neo' (built-in themes: classic, neon, minimal; or a .json, .yaml or .toml theme file)"
+        );
+    }
+
+    #[test]
+    fn inline_theme_overrides_its_base() {
+        let p = project(
+            r##"{
+              "extends": "neon",
+              "font": "/fonts/Bangers.ttf",
+              "text_color": "yellow",
+              "answer": { "size": 60, "y": 80 }
+            }"##,
+        );
+        let look = resolve_look(&p).unwrap();
+
+        assert_eq!(look.background, "#0d0221");
+        // The global colour wins over the base per-text colour
+        assert_eq!(look.answer.color, "yellow");
+        assert_eq!(look.answer.border_color.as_deref(), Some("#ff2a6d"));
+        assert_eq!((look.answer.size, look.answer.y), (60, Some(80)));
+        assert_eq!(look.countdown.size, 96);
+        assert_eq!(look.title.font.as_deref(), Some("/fonts/Bangers.ttf"));
+    }
+
+    #[test]
+    fn loads_theme_files_relative_to_themselves() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("soiree.yaml");
+        fs::write(
+            &path,
+            "extends: minimal\nfont: fonts/Title.otf\nguess_image: bg.png\ncountdown: { size: 120 }\n",
+        )
+        .unwrap();
+
+        let p = project(&format!("{:?}", path.to_string_lossy()));
+        let look = resolve_look(&p).unwrap();
+        assert_eq!(look.countdown.size, 120);
+        assert_eq!(look.background, "#f5f5f0");
+        assert_eq!(
+            look.hint.font,
+            Some(
+                dir.path()
+                    .join("fonts/Title.otf")
+                    .to_string_lossy()
+                    .into_owned()
+            )
+        );
+        assert_eq!(
+            look.guess_image,
+            Some(dir.path().join("bg.png").to_string_lossy().into_owned())
+        );
+
+        assert!(is_theme_file("themes/Soiree.YML"));
+        assert!(!is_theme_file("neon"));
+    }
+}
@@ -1,98 +1,550 @@
 // src/timecode.rs
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, anyhow, bail};
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
 
-/// Parse a timecode formatted as `HH:MM:SS.mmm` into milliseconds.
+/// A timecode in a source video (`start`, `end`) or a duration (`guess_duration`, ...).
+///
+/// Parsed from any form accepted by [`parse_timecode_ms`] and serialized as
+/// `HH:MM:SS.mmm` (or `HH:MM:SS:FF` when written with frames). Frames of a SMPTE
+/// timecode are kept as is: they only become milliseconds with the output frame
+/// rate, see [`Timecode::as_ms`]. A timecode with frames is always a whole
+/// number of seconds plus frames, so it prints back as `HH:MM:SS:FF`.
+///
+/// Positions and lengths share this type on purpose: project files write both
+/// with the same syntax (including SMPTE frames), so they parse, validate and
+/// serialize identically. There is deliberately no `+` operator: adding frames
+/// needs a frame rate, see [`Timecode::add_at`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Timecode {
+    ms: u64,
+    frames: u32,
+}
+
+impl Timecode {
+    pub const ZERO: Timecode = Timecode { ms: 0, frames: 0 };
+
+    pub const fn from_ms(ms: u64) -> Self {
+        Timecode { ms, frames: 0 }
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Timecode::from_ms(secs * 1_000)
+    }
+
+    /// Milliseconds, converting frames at `fps` (rounded to the nearest millisecond).
+    pub fn as_ms(self, fps: u32) -> u64 {
+        if self.frames == 0 {
+            return self.ms;
+        }
+        let fps = fps.max(1) as u64;
+        self.ms
+            .saturating_add((self.frames as u64 * 1_000 + fps / 2) / fps)
+    }
+
+    /// Frames on top of [`Timecode::as_ms`]'s whole milliseconds (SMPTE only).
+    pub fn frames(self) -> u32 {
+        self.frames
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.ms == 0 && self.frames == 0
+    }
+
+    /// Fail if the frames of a SMPTE timecode do not exist at `fps`.
+    pub fn check_fps(self, fps: u32) -> Result<()> {
+        if self.frames > 0 {
+            if fps == 0 {
+                bail!("frame-based timecodes need fps > 0");
+            }
+            if self.frames >= fps {
+                bail!("frames out of range (0..{}) at {fps} fps", fps - 1);
+            }
+        }
+        Ok(())
+    }
+
+    /// Same instant in milliseconds only (`HH:MM:SS.mmm`), frames converted at `fps`.
+    pub fn normalized(self, fps: u32) -> Self {
+        Timecode::from_ms(self.as_ms(fps))
+    }
+
+    /// Sum of two timecodes, frames counted at `fps`, or `None` on overflow.
+    ///
+    /// Two SMPTE (or whole second) timecodes stay SMPTE, frames carried into
+    /// seconds; otherwise frames are converted to milliseconds.
+    pub fn add_at(self, rhs: Timecode, fps: u32) -> Option<Timecode> {
+        if self.frames == 0 && rhs.frames == 0 {
+            return self.ms.checked_add(rhs.ms).map(Timecode::from_ms);
+        }
+        if !self.ms.is_multiple_of(1_000) || !rhs.ms.is_multiple_of(1_000) {
+            return self
+                .as_ms(fps)
+                .checked_add(rhs.as_ms(fps))
+                .map(Timecode::from_ms);
+        }
+        let fps = fps.max(1);
+        let frames = self.frames.checked_add(rhs.frames)?;
+        let ms = self
+            .ms
+            .checked_add(rhs.ms)?
+            .checked_add((frames / fps) as u64 * 1_000)?;
+        Some(Timecode {
+            ms,
+            frames: frames % fps,
+        })
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.frames > 0 {
+            let secs = self.ms / 1_000;
+            let (hh, mm, ss) = (secs / 3_600, (secs % 3_600) / 60, secs % 60);
+            write!(f, "{hh:02}:{mm:02}:{ss:02}:{:02}", self.frames)
+        } else {
+            f.write_str(&format_timecode_ms(self.ms))
+        }
+    }
+}
+
+impl FromStr for Timecode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let t = s.trim();
+        let parsed = if t.is_empty() {
+            Err(anyhow!("empty timecode"))
+        } else if t.contains(':') {
+            parse_clock(t)
+        } else if t.ends_with(|c: char| c.is_ascii_alphabetic()) {
+            parse_units(t).map(Timecode::from_ms)
+        } else {
+            parse_seconds(t, 1_000).map(Timecode::from_ms)
+        };
+        parsed.with_context(|| {
+            format!(
+                "invalid timecode '{t}' (e.g. 10s, 1m30s, 90.5, MM:SS, HH:MM:SS.mmm, HH:MM:SS:FF)"
+            )
+        })
+    }
+}
+
+impl Serialize for Timecode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timecode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|e: anyhow::Error| serde::de::Error::custom(format!("{e:#}")))
+    }
+}
+
+/// Regex (JSON Schema / ECMA 262 flavour) of the forms accepted by [`Timecode`]'s parser.
+pub fn timecode_pattern() -> String {
+    let num = r"\d+(\.\d{1,3})?";
+    let units = format!(
+        "{num}h({num}m)?({num}s)?({num}ms)?|{num}m({num}s)?({num}ms)?|{num}s({num}ms)?|{num}ms"
+    );
+    format!(
+        r"^(\d+:[0-5]?\d(\.\d{{1,3}})?|\d+:[0-5]?\d:[0-5]?\d(\.\d{{1,3}})?|\d+:[0-5]?\d:[0-5]?\d:\d{{1,2}}|{num}|{units})$"
+    )
+}
+
+impl JsonSchema for Timecode {
+    fn schema_name() -> Cow<'static, str> {
+        "Timecode".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Timecode or duration: HH:MM:SS.mmm, H:MM:SS, MM:SS, seconds (90.5), units (1m30s) or SMPTE HH:MM:SS:FF",
+            "pattern": timecode_pattern(),
+            "examples": ["00:01:30.000", "1:30", "90.5", "1m30s", "00:01:30:12"]
+        })
+    }
+}
+
+/// Parse a timecode or duration into milliseconds.
+///
+/// Accepted forms:
+/// - `HH:MM:SS.mmm`, `H:MM:SS`, `MM:SS` (fraction of second optional)
+/// - `HH:MM:SS:FF` (SMPTE, `FF` frames at `fps`)
+/// - plain seconds: `90`, `90.5`
+/// - units: `10s`, `1m30s`, `1h2m`, `1.5m`, `500ms`
 ///
 /// Examples:
 /// - "00:00:01.000" -> 1000
 /// - "01:02:03.004" -> 3723004
-pub fn parse_timecode_ms(s: &str) -> Result<u64> {
-    // Strict format: exactly 12 chars "HH:MM:SS.mmm"
-    // Example: 0 1 : 3 4 : 6 7 . 9 10 11
-    if s.len() != 12 {
-        bail!("invalid timecode length (expected HH:MM:SS.mmm): '{s}'");
-    }
-    let bytes = s.as_bytes();
-    if bytes[2] != b':' || bytes[5] != b':' || bytes[8] != b'.' {
-        bail!("invalid timecode separators (expected HH:MM:SS.mmm): '{s}'");
+/// - "1m30s" -> 90000
+/// - "00:00:01:15" at 30 fps -> 1500
+pub fn parse_timecode_ms(s: &str, fps: u32) -> Result<u64> {
+    let tc: Timecode = s.parse()?;
+    tc.check_fps(fps)
+        .with_context(|| format!("invalid timecode '{}'", s.trim()))?;
+    Ok(tc.as_ms(fps))
+}
+
+/// Format milliseconds as `HH:MM:SS.mmm` (inverse of [`parse_timecode_ms`]).
+///
+/// Examples:
+/// - 1000 -> "00:00:01.000"
+/// - 3723004 -> "01:02:03.004"
+pub fn format_timecode_ms(ms: u64) -> String {
+    let hh = ms / 3_600_000;
+    let mm = (ms % 3_600_000) / 60_000;
+    let ss = (ms % 60_000) / 1_000;
+    let mmm = ms % 1_000;
+    format!("{hh:02}:{mm:02}:{ss:02}.{mmm:03}")
+}
+
+/// Format milliseconds as a short clock, truncated to the second:
+/// `MM:SS`, or `H:MM:SS` from one hour (as shown by video players).
+///
+/// Examples:
+/// - 65000 -> "01:05"
+/// - 3723004 -> "1:02:03"
+pub fn format_clock_ms(ms: u64) -> String {
+    let h = ms / 3_600_000;
+    let mm = (ms % 3_600_000) / 60_000;
+    let ss = (ms % 60_000) / 1_000;
+    if h > 0 {
+        format!("{h}:{mm:02}:{ss:02}")
+    } else {
+        format!("{mm:02}:{ss:02}")
     }
+}
 
-    let hh =
-        parse_2_digits(&s[0..2]).with_context(|| format!("invalid hours in timecode '{s}'"))?;
-    let mm =
-        parse_2_digits(&s[3..5]).with_context(|| format!("invalid minutes in timecode '{s}'"))?;
-    let ss =
-        parse_2_digits(&s[6..8]).with_context(|| format!("invalid seconds in timecode '{s}'"))?;
-    let mmm = parse_3_digits(&s[9..12])
-        .with_context(|| format!("invalid milliseconds in timecode '{s}'"))?;
+/// `MM:SS`, `H:MM:SS` (each with an optional `.mmm`) or SMPTE `HH:MM:SS:FF`.
+fn parse_clock(t: &str) -> Result<Timecode> {
+    let parts: Vec<&str> = t.split(':').collect();
+    let (hh, mm, ss, ff) = match parts.as_slice() {
+        [m, s] => ("0", *m, *s, None),
+        [h, m, s] => (*h, *m, *s, None),
+        [h, m, s, f] => (*h, *m, *s, Some(*f)),
+        _ => bail!("too many ':' separators"),
+    };
 
-    if mm > 59 {
-        bail!("minutes out of range (0..59) in timecode '{s}'");
+    let hh = parse_digits(hh, None).context("invalid hours")?;
+    // Leading component of `MM:SS` may exceed 59 (e.g. 90:00)
+    let mm = parse_digits(mm, (parts.len() > 2).then_some(2)).context("invalid minutes")?;
+    if parts.len() > 2 && mm > 59 {
+        bail!("minutes out of range (0..59)");
     }
+
+    let (ss, frac) = match ss.split_once('.') {
+        Some(_) if ff.is_some() => bail!("frame-based timecodes cannot have milliseconds"),
+        Some((sec, frac)) => (sec, parse_fraction_ms(frac)?),
+        None => (ss, 0),
+    };
+    let ss = parse_digits(ss, Some(2)).context("invalid seconds")?;
     if ss > 59 {
-        bail!("seconds out of range (0..59) in timecode '{s}'");
+        bail!("seconds out of range (0..59)");
     }
-    // mmm is 0..999 by construction (3 digits)
 
-    let total_ms =
-        (hh as u64) * 3_600_000 + (mm as u64) * 60_000 + (ss as u64) * 1_000 + (mmm as u64);
+    let frames = match ff {
+        Some(f) => parse_digits(f, Some(2)).context("invalid frames")? as u32,
+        None => 0,
+    };
 
-    Ok(total_ms)
+    // Hours and `MM:SS` minutes have no length limit
+    let ms = hh
+        .checked_mul(3_600_000)
+        .zip(mm.checked_mul(60_000))
+        .and_then(|(h, m)| h.checked_add(m))
+        .and_then(|ms| ms.checked_add(ss * 1_000 + frac))
+        .context("timecode too large")?;
+    Ok(Timecode { ms, frames })
 }
 
-fn parse_2_digits(s: &str) -> Result<u16> {
-    if s.len() != 2 || !s.chars().all(|c| c.is_ascii_digit()) {
-        bail!("expected 2 digits, got '{s}'");
+/// `1h2m3.5s`, `10s`, `500ms`: numbers followed by a unit, largest unit first.
+fn parse_units(t: &str) -> Result<u64> {
+    const UNITS: [(&str, u64); 4] = [("h", 3_600_000), ("m", 60_000), ("s", 1_000), ("ms", 1)];
+
+    let mut total: u64 = 0;
+    let mut next_unit = 0;
+    let mut rest = t;
+    while !rest.is_empty() {
+        let num_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .context("missing unit (h, m, s or ms)")?;
+        let unit_len = rest[num_len..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len() - num_len);
+        let (num, unit) = (&rest[..num_len], &rest[num_len..num_len + unit_len]);
+
+        let Some(idx) = UNITS.iter().position(|(u, _)| *u == unit) else {
+            bail!("unknown unit '{unit}' (expected h, m, s or ms)");
+        };
+        if idx < next_unit {
+            bail!("units must go from largest to smallest, each at most once");
+        }
+        next_unit = idx + 1;
+
+        total = total
+            .checked_add(parse_seconds(num, UNITS[idx].1)?)
+            .context("timecode too large")?;
+        rest = &rest[num_len + unit_len..];
     }
-    Ok(s.parse::<u16>()?)
+    Ok(total)
 }
 
-fn parse_3_digits(s: &str) -> Result<u16> {
-    if s.len() != 3 || !s.chars().all(|c| c.is_ascii_digit()) {
-        bail!("expected 3 digits, got '{s}'");
+/// Decimal number (`90`, `90.5`) of units of `unit_ms` milliseconds.
+fn parse_seconds(t: &str, unit_ms: u64) -> Result<u64> {
+    let (int, frac) = match t.split_once('.') {
+        Some((int, frac)) => (int, parse_fraction_ms(frac)?),
+        None => (t, 0),
+    };
+    let int = parse_digits(int, None)?;
+    int.checked_mul(unit_ms)
+        .and_then(|ms| ms.checked_add(frac * unit_ms / 1_000))
+        .context("timecode too large")
+}
+
+/// Up to 3 digits after the decimal point, as thousandths: "5" -> 500, "25" -> 250.
+fn parse_fraction_ms(frac: &str) -> Result<u64> {
+    if frac.is_empty() || frac.len() > 3 {
+        bail!("expected 1 to 3 decimals, got '{frac}'");
+    }
+    let value = parse_digits(frac, None)?;
+    Ok(value * 10u64.pow(3 - frac.len() as u32))
+}
+
+/// Digits only, with an optional maximum length.
+fn parse_digits(s: &str, max_len: Option<usize>) -> Result<u64> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        bail!("expected digits, got '{s}'");
+    }
+    if let Some(max) = max_len
+        && s.len() > max
+    {
+        bail!("expected at most {max} digits, got '{s}'");
     }
-    Ok(s.parse::<u16>()?)
+    s.parse::<u64>()
+        .with_context(|| format!("number too large: '{s}'"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::tc;
+
+    fn ms(s: &str) -> u64 {
+        parse_timecode_ms(s, 30).unwrap()
+    }
 
     #[test]
     fn parses_valid_timecodes() {
-        assert_eq!(parse_timecode_ms("00:00:00.000").unwrap(), 0);
-        assert_eq!(parse_timecode_ms("00:00:01.000").unwrap(), 1_000);
-        assert_eq!(parse_timecode_ms("00:01:00.000").unwrap(), 60_000);
-        assert_eq!(parse_timecode_ms("01:00:00.000").unwrap(), 3_600_000);
-        assert_eq!(parse_timecode_ms("01:02:03.004").unwrap(), 3_723_004);
-        assert_eq!(parse_timecode_ms("99:59:59.999").unwrap(), 359_999_999);
+        assert_eq!(ms("00:00:00.000"), 0);
+        assert_eq!(ms("00:00:01.000"), 1_000);
+        assert_eq!(ms("00:01:00.000"), 60_000);
+        assert_eq!(ms("01:00:00.000"), 3_600_000);
+        assert_eq!(ms("01:02:03.004"), 3_723_004);
+        assert_eq!(ms("99:59:59.999"), 359_999_999);
+    }
+
+    #[test]
+    fn parses_short_clock_forms() {
+        assert_eq!(ms("1:30"), 90_000);
+        assert_eq!(ms("90:00"), 5_400_000);
+        assert_eq!(ms("0:00:01.5"), 1_500);
+        assert_eq!(ms("1:02:03"), 3_723_000);
+        assert_eq!(ms("00:00:01.25"), 1_250);
+    }
+
+    #[test]
+    fn parses_plain_seconds_and_units() {
+        assert_eq!(ms("90"), 90_000);
+        assert_eq!(ms("90.5"), 90_500);
+        assert_eq!(ms(" 10s "), 10_000);
+        assert_eq!(ms("1m30s"), 90_000);
+        assert_eq!(ms("1h2m3.5s"), 3_723_500);
+        assert_eq!(ms("1.5m"), 90_000);
+        assert_eq!(ms("500ms"), 500);
+        assert_eq!(ms("2s250ms"), 2_250);
     }
 
     #[test]
-    fn rejects_bad_length() {
-        assert!(parse_timecode_ms("0:00:01.000").is_err());
-        assert!(parse_timecode_ms("00:00:01.00").is_err());
-        assert!(parse_timecode_ms("").is_err());
+    fn parses_smpte_frames_at_output_fps() {
+        assert_eq!(parse_timecode_ms("00:00:01:15", 30).unwrap(), 1_500);
+        assert_eq!(parse_timecode_ms("00:00:01:12", 25).unwrap(), 1_480);
+        assert!(parse_timecode_ms("00:00:01:25", 25).is_err());
+        assert!(parse_timecode_ms("00:00:01:10", 0).is_err());
+        assert!(parse_timecode_ms("00:00:01.000:10", 30).is_err());
+    }
+
+    #[test]
+    fn timecode_keeps_frames_until_fps_is_known() {
+        let tc: Timecode = "00:00:01:12".parse().unwrap();
+        assert_eq!(tc.frames(), 12);
+        assert_eq!(tc.as_ms(24), 1_500);
+        assert_eq!(tc.as_ms(25), 1_480);
+        assert!(tc.check_fps(12).is_err());
+        assert_eq!(tc.to_string(), "00:00:01:12");
+        assert_eq!(tc.normalized(24).to_string(), "00:00:01.500");
+    }
+
+    #[test]
+    fn timecode_serde_round_trip() {
+        let tc: Timecode = serde_json::from_str("\"1m30s\"").unwrap();
+        assert_eq!(tc, Timecode::from_secs(90));
+        assert_eq!(serde_json::to_string(&tc).unwrap(), "\"00:01:30.000\"");
+
+        let err = serde_json::from_str::<Timecode>("\"soon\"").unwrap_err();
+        assert!(err.to_string().contains("invalid timecode 'soon'"), "{err}");
+    }
+
+    #[test]
+    fn adds_frames_at_a_frame_rate() {
+        // Frames carry into seconds and the sum prints back as a valid timecode
+        let sum = tc("00:00:01:20").add_at(tc("00:00:01:20"), 25).unwrap();
+        assert_eq!(sum.to_string(), "00:00:03:15");
+        assert_eq!(tc(&sum.to_string()), sum);
+        sum.check_fps(25).unwrap();
+        assert_eq!(sum.as_ms(25), 3_600);
+
+        assert_eq!(
+            tc("00:00:01:20").add_at(Timecode::from_secs(2), 25),
+            Some(tc("00:00:03:20"))
+        );
+        // Milliseconds and frames only mix once frames are converted
+        let mixed = tc("00:00:01:20").add_at(Timecode::from_ms(500), 25);
+        assert_eq!(mixed.unwrap().to_string(), "00:00:02.300");
+
+        assert_eq!(
+            Timecode::from_secs(1).add_at(Timecode::from_secs(2), 30),
+            Some(Timecode::from_secs(3))
+        );
+        assert!(Timecode::ZERO.is_zero());
+    }
+
+    #[test]
+    fn addition_reports_overflow() {
+        let max = Timecode::from_ms(u64::MAX);
+        assert_eq!(max.add_at(Timecode::from_ms(1), 25), None);
+        assert_eq!(
+            Timecode::from_ms(u64::MAX - 500).add_at(tc("00:00:00:20"), 25),
+            None
+        );
+        assert_eq!(
+            Timecode::from_ms(u64::MAX / 1_000 * 1_000).add_at(tc("00:00:00:20"), 25),
+            Some(Timecode {
+                ms: u64::MAX / 1_000 * 1_000,
+                frames: 20
+            })
+        );
+    }
+
+    #[test]
+    fn schema_pattern_matches_parser() {
+        let re = regex::Regex::new(&timecode_pattern()).unwrap();
+        for s in [
+            "00:00:01.000",
+            "1:30",
+            "90:00",
+            "1:02:03",
+            "0:00:01.5",
+            "00:00:01:12",
+            "90",
+            "90.5",
+            "10s",
+            "1m30s",
+            "1h2m3.5s",
+            "1.5m",
+            "500ms",
+            "2s250ms",
+        ] {
+            assert!(s.parse::<Timecode>().is_ok(), "parser rejects '{s}'");
+            assert!(re.is_match(s), "pattern rejects '{s}'");
+        }
+        for s in [
+            "",
+            "banana",
+            "1:2:3:4:5",
+            "00:60:00",
+            "00:00:01.0000",
+            "1s1m",
+            "10x",
+            "-5",
+            "1:75",
+        ] {
+            assert!(s.parse::<Timecode>().is_err(), "parser accepts '{s}'");
+            assert!(!re.is_match(s), "pattern accepts '{s}'");
+        }
+    }
+
+    #[test]
+    fn formats_timecodes() {
+        assert_eq!(format_timecode_ms(0), "00:00:00.000");
+        assert_eq!(format_timecode_ms(3_723_004), "01:02:03.004");
+        assert_eq!(ms(&format_timecode_ms(359_999_999)), 359_999_999);
+
+        assert_eq!(format_clock_ms(65_999), "01:05");
+        assert_eq!(format_clock_ms(3_723_004), "1:02:03");
+    }
+
+    #[test]
+    fn normalizes_to_canonical_form() {
+        let norm = |s: &str| s.parse::<Timecode>().unwrap().normalized(30).to_string();
+        assert_eq!(norm("1m30s"), "00:01:30.000");
+        assert_eq!(norm("00:00:02:15"), "00:00:02.500");
+        assert!("soon".parse::<Timecode>().is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for bad in [
+            "",
+            "1:2:3:4:5",
+            "00:00:01.0000",
+            "00:00:01.",
+            "1.2.3",
+            "10x",
+            "1s1m",
+            "1m1m",
+            "m",
+            "-5",
+            "1 m",
+        ] {
+            assert!(parse_timecode_ms(bad, 30).is_err(), "accepted '{bad}'");
+        }
     }
 
     #[test]
     fn rejects_bad_separators() {
-        assert!(parse_timecode_ms("00-00:01.000").is_err());
-        assert!(parse_timecode_ms("00:00-01.000").is_err());
-        assert!(parse_timecode_ms("00:00:01,000").is_err());
+        assert!(parse_timecode_ms("00-00:01.000", 30).is_err());
+        assert!(parse_timecode_ms("00:00-01.000", 30).is_err());
+        assert!(parse_timecode_ms("00:00:01,000", 30).is_err());
     }
 
     #[test]
     fn rejects_non_digits() {
-        assert!(parse_timecode_ms("aa:00:01.000").is_err());
-        assert!(parse_timecode_ms("00:bb:01.000").is_err());
-        assert!(parse_timecode_ms("00:00:cc.000").is_err());
-        assert!(parse_timecode_ms("00:00:01.xxx").is_err());
+        assert!(parse_timecode_ms("aa:00:01.000", 30).is_err());
+        assert!(parse_timecode_ms("00:bb:01.000", 30).is_err());
+        assert!(parse_timecode_ms("00:00:cc.000", 30).is_err());
+        assert!(parse_timecode_ms("00:00:01.xxx", 30).is_err());
     }
 
     #[test]
     fn rejects_out_of_range_minutes_seconds() {
-        assert!(parse_timecode_ms("00:60:00.000").is_err());
-        assert!(parse_timecode_ms("00:00:60.000").is_err());
+        assert!(parse_timecode_ms("00:60:00.000", 30).is_err());
+        assert!(parse_timecode_ms("00:00:60.000", 30).is_err());
+        assert!(parse_timecode_ms("1:75", 30).is_err());
+    }
+
+    #[test]
+    fn rejects_overflow() {
+        assert!(parse_timecode_ms("99999999999999999999s", 30).is_err());
+        assert!(parse_timecode_ms("9999999999999999:00:00", 30).is_err());
+        assert!(parse_timecode_ms("999999999999999:00", 30).is_err());
+        assert!(parse_timecode_ms("307445734561825:59.999", 30).is_err());
     }
 }
@@ -0,0 +1,210 @@
+// src/timeline.rs
+
+use anyhow::{Context, Result};
+
+use crate::ffmpeg_command::{resolve_clip_timings, round_title_ms};
+use crate::model::{Clip, Project, Round};
+
+/// Time range in the rendered video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Where one clip lands in the rendered video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipCue<'a> {
+    /// 1-based position in playing order
+    pub number: usize,
+    pub clip: &'a Clip,
+    /// Index in `project.rounds` (`None` for top-level clips)
+    pub round: Option<usize>,
+    /// Start of the guess phase
+    pub guess_ms: u64,
+    /// Start of the hint phase, if the clip has one
+    pub hint_ms: Option<u64>,
+    /// Start of the reveal phase
+    pub reveal_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Where one round lands in the rendered video: its title card, then its clips.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundCue<'a> {
+    pub round: &'a Round,
+    pub title: Span,
+    /// End of the last clip of the round
+    pub end_ms: u64,
+}
+
+/// Layout of the rendered video:
+/// intro + top-level clips + (title card + round clips)... + outro.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeline<'a> {
+    pub intro: Option<Span>,
+    pub clips: Vec<ClipCue<'a>>,
+    pub rounds: Vec<RoundCue<'a>>,
+    pub outro: Option<Span>,
+    pub total_ms: u64,
+}
+
+/// Compute when every part of a project starts in the rendered video.
+pub fn timeline(p: &Project) -> Result<Timeline<'_>> {
+    let fps = p.fps();
+    let mut at = 0;
+    let mut next = |len: u64| {
+        let span = Span {
+            start_ms: at,
+            end_ms: at + len,
+        };
+        at += len;
+        span
+    };
+
+    let intro = p.intro.as_ref().map(|i| next(i.duration.as_ms(fps)));
+
+    let mut clips = Vec::new();
+    let mut rounds = Vec::new();
+    let top_level = p.clips.iter().map(|c| (None, c));
+    let in_rounds = p
+        .rounds
+        .iter()
+        .enumerate()
+        .flat_map(|(r, round)| round.clips.iter().map(move |c| (Some(r), c)));
+
+    let paths = p.clip_paths().into_iter().map(|(at, _)| at);
+    for (i, ((round, clip), at)) in top_level.chain(in_rounds).zip(paths).enumerate() {
+        // Title cards come right before the first clip of their round
+        while round.is_some_and(|r| rounds.len() <= r) {
+            let round = &p.rounds[rounds.len()];
+            let title = next(round_title_ms(round, fps));
+            rounds.push(RoundCue {
+                round,
+                title,
+                end_ms: title.end_ms,
+            });
+        }
+
+        let timings = resolve_clip_timings(clip, &p.timings, fps)
+            .with_context(|| format!("{at} has invalid timings"))?;
+        let guess = next(timings.guess_ms);
+        let hint = (timings.hint_ms > 0).then(|| next(timings.hint_ms));
+        let reveal = next(timings.reveal_ms);
+        clips.push(ClipCue {
+            number: i + 1,
+            clip,
+            round,
+            guess_ms: guess.start_ms,
+            hint_ms: hint.map(|h| h.start_ms),
+            reveal_ms: reveal.start_ms,
+            end_ms: reveal.end_ms,
+        });
+        if let Some(r) = round {
+            rounds[r].end_ms = reveal.end_ms;
+        }
+    }
+
+    // Trailing rounds without clips still get their title card
+    while rounds.len() < p.rounds.len() {
+        let round = &p.rounds[rounds.len()];
+        let title = next(round_title_ms(round, fps));
+        rounds.push(RoundCue {
+            round,
+            title,
+            end_ms: title.end_ms,
+        });
+    }
+
+    let outro = p.outro.as_ref().map(|o| next(o.duration.as_ms(fps)));
+
+    Ok(Timeline {
+        intro,
+        clips,
+        rounds,
+        outro,
+        total_ms: at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fixtures::{clip, intro, outro, round, sample_project, tc};
+
+    fn project() -> Project {
+        let mut p = sample_project();
+        p.intro = Some(intro("T", "5s"));
+        p.clips[0].guess_duration = Some(tc("20s"));
+        p.rounds = vec![
+            round(
+                "R1",
+                vec![
+                    clip("videos/b.mp4", "0s", "B"),
+                    Clip {
+                        end: Some(tc("1m12s")),
+                        hint_duration: Some(tc("1s")),
+                        ..clip("videos/c.mp4", "1m", "C")
+                    },
+                ],
+            ),
+            Round {
+                duration: Some(tc("2s")),
+                ..round("R2", Vec::new())
+            },
+        ];
+        p.outro = Some(outro("Fin", "4s"));
+        p
+    }
+
+    #[test]
+    fn lays_out_every_part_in_order() {
+        let p = project();
+        let t = timeline(&p).unwrap();
+
+        assert_eq!(
+            t.intro,
+            Some(Span {
+                start_ms: 0,
+                end_ms: 5_000
+            })
+        );
+
+        let cues: Vec<_> = t
+            .clips
+            .iter()
+            .map(|c| {
+                (
+                    c.number,
+                    c.round,
+                    c.guess_ms,
+                    c.hint_ms,
+                    c.reveal_ms,
+                    c.end_ms,
+                )
+            })
+            .collect();
+        assert_eq!(
+            cues,
+            [
+                (1, None, 5_000, None, 25_000, 30_000),
+                // R1 title card: 30 s → 33 s
+                (2, Some(0), 33_000, None, 43_000, 48_000),
+                // 1 s hint, taken from the reveal (the clip ends at 1m12s)
+                (3, Some(0), 48_000, Some(58_000), 59_000, 60_000),
+            ]
+        );
+
+        assert_eq!(t.rounds[0].title.start_ms, 30_000);
+        assert_eq!(t.rounds[0].end_ms, 60_000);
+        assert_eq!(t.rounds[1].title.end_ms, 62_000);
+        assert_eq!(t.rounds[1].end_ms, 62_000);
+        assert_eq!(t.outro.unwrap().start_ms, 62_000);
+        assert_eq!(t.total_ms, 66_000);
+        assert_eq!(
+            crate::ffmpeg_command::expected_duration_ms(&p).unwrap(),
+            66_000
+        );
+    }
+}
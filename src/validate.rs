@@ -1,77 +1,622 @@
 // src/validate.rs
-use anyhow::{Context, Result, bail};
+use anyhow::{Result, bail};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
 
-use crate::model::Project;
-use crate::timecode::parse_timecode_ms;
+use crate::diagnostic;
+use crate::ffmpeg_command::resolve_clip_timings;
+use crate::model::{Answer, Clip, Project, Theme, ThemeRef};
+use crate::probe::{self, MediaInfo};
+use crate::quiz::{CHOICES, MAX_OPTIONS_Y, OPTION_SPACING, distractor_candidates};
+use crate::theme::{BUILTIN_THEMES, builtin_theme, is_theme_file, project_theme};
+use crate::timecode::{Timecode, format_timecode_ms};
 
-pub fn validate_project(p: &Project) -> Result<()> {
-    if p.output.path.trim().is_empty() {
-        bail!("output.path must not be empty");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One problem found in a project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Issue {
+    pub severity: Severity,
+    /// JSON path of the offending value (e.g. `clips[7].start`), empty for the whole file
+    pub path: String,
+    pub message: String,
+    /// Position of the value in the source file, once located
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} {}", self.path, self.message)
+        }
+    }
+}
+
+/// Every error and warning found in a project, in document order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    pub fn error(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, path.into(), message.into());
+    }
+
+    pub fn warning(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, path.into(), message.into());
+    }
+
+    fn push(&mut self, severity: Severity, path: String, message: String) {
+        self.issues.push(Issue {
+            severity,
+            path,
+            message,
+            line: None,
+            column: None,
+        });
+    }
+
+    /// Fill in the line/column of every issue from the JSON source it was loaded from.
+    pub fn locate(&mut self, source: &str) {
+        for issue in &mut self.issues {
+            if issue.line.is_none()
+                && let Some(pos) = diagnostic::locate(source, &issue.path)
+            {
+                issue.line = Some(pos.line);
+                issue.column = Some(pos.column);
+            }
+        }
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(|i| i.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Append the issues of another report.
+    pub fn merge(&mut self, other: ValidationReport) {
+        self.issues.extend(other.issues);
     }
 
+    /// Fail with every error (one per line) if there is any; warnings are ignored.
+    pub fn into_result(self) -> Result<()> {
+        if !self.has_errors() {
+            return Ok(());
+        }
+        let lines: Vec<String> = self.errors().map(|i| i.to_string()).collect();
+        bail!("{}", lines.join("\n"))
+    }
+}
+
+/// Validate a project, failing with every error found.
+pub fn validate_project(p: &Project) -> Result<()> {
+    check_project(p).into_result()
+}
+
+/// Check a project and collect every error and warning.
+pub fn check_project(p: &Project) -> ValidationReport {
+    let mut r = ValidationReport::default();
+    let fps = p.fps();
+
+    non_empty(&mut r, "output.path", &p.output.path);
+
     // intro (optional)
     if let Some(intro) = p.intro.as_ref() {
-        if intro.background.trim().is_empty() {
-            bail!("intro.background must not be empty");
+        non_empty(&mut r, "intro.background", &intro.background);
+        non_empty(&mut r, "intro.music", &intro.music);
+        non_empty(&mut r, "intro.title", &intro.title);
+        check_duration(&mut r, "intro.duration", intro.duration, fps);
+    }
+
+    // outro (optional)
+    if let Some(outro) = p.outro.as_ref() {
+        non_empty(&mut r, "outro.background", &outro.background);
+        non_empty(&mut r, "outro.music", &outro.music);
+        non_empty(&mut r, "outro.text", &outro.text);
+        check_duration(&mut r, "outro.duration", outro.duration, fps);
+    }
+
+    // timings: parse + strictly > 0
+    let guess_ms = check_duration(
+        &mut r,
+        "timings.guess_duration",
+        p.timings.guess_duration,
+        fps,
+    );
+    check_duration(
+        &mut r,
+        "timings.reveal_duration",
+        p.timings.reveal_duration,
+        fps,
+    );
+    let hint_ms = p
+        .timings
+        .hint_duration
+        .and_then(|h| check_duration(&mut r, "timings.hint_duration", h, fps));
+
+    // clips: at least one (top-level or inside rounds)
+    if p.all_clips().next().is_none() {
+        r.error("clips", "must not be empty");
+    }
+
+    for (i, c) in p.clips.iter().enumerate() {
+        check_clip(&mut r, &format!("clips[{i}]"), c, guess_ms, hint_ms, fps);
+    }
+
+    // rounds (optional)
+    for (ri, round) in p.rounds.iter().enumerate() {
+        let at = format!("rounds[{ri}]");
+        non_empty(&mut r, &format!("{at}.title"), &round.title);
+        if let Some(bg) = round.background.as_deref() {
+            non_empty(&mut r, &format!("{at}.background"), bg);
+        }
+        if let Some(d) = round.duration {
+            check_duration(&mut r, &format!("{at}.duration"), d, fps);
         }
-        if intro.music.trim().is_empty() {
-            bail!("intro.music must not be empty");
+        if round.clips.is_empty() {
+            r.error(format!("{at}.clips"), "must not be empty");
         }
-        if intro.title.trim().is_empty() {
-            bail!("intro.title must not be empty");
+        for (i, c) in round.clips.iter().enumerate() {
+            check_clip(
+                &mut r,
+                &format!("{at}.clips[{i}]"),
+                c,
+                guess_ms,
+                hint_ms,
+                fps,
+            );
         }
+    }
+
+    // Optional output validation (light, V1):
+    if p.output.fps == Some(0) {
+        r.error("output.fps", "must be > 0");
+    }
+
+    if let Some(res) = p.output.resolution.as_deref()
+        && !is_resolution(res.trim())
+    {
+        r.error(
+            "output.resolution",
+            "must be formatted as WIDTHxHEIGHT (e.g. 1920x1080)",
+        );
+    }
 
-        let intro_ms =
-            parse_timecode_ms(intro.duration.trim()).context("intro.duration is invalid")?;
-        if intro_ms == 0 {
-            bail!("intro.duration must be > 0");
+    match &p.theme {
+        Some(ThemeRef::Name(name))
+            if builtin_theme(name.trim()).is_none() && !is_theme_file(name) =>
+        {
+            r.error(
+                "theme",
+                format!(
+                    "is neither a built-in theme ({}) nor a .json, .yaml or .toml theme file",
+                    BUILTIN_THEMES.join(", ")
+                ),
+            )
         }
+        Some(ThemeRef::Inline(theme)) => check_theme(&mut r, "theme", theme),
+        _ => {}
     }
 
-    // timings: parse + strictly > 0
-    let guess_ms = parse_timecode_ms(p.timings.guess_duration.trim())
-        .context("timings.guess_duration is invalid")?;
-    let reveal_ms = parse_timecode_ms(p.timings.reveal_duration.trim())
-        .context("timings.reveal_duration is invalid")?;
+    check_duplicates(&mut r, p);
+    check_quiz(&mut r, p);
+
+    r
+}
 
-    if guess_ms == 0 {
-        bail!("timings.guess_duration must be > 0");
+/// Check one clip; `at` is its JSON path (e.g. `clips[3]`).
+fn check_clip(
+    r: &mut ValidationReport,
+    at: &str,
+    c: &Clip,
+    default_guess_ms: Option<u64>,
+    default_hint_ms: Option<u64>,
+    fps: u32,
+) {
+    non_empty(r, &format!("{at}.video"), &c.video);
+    match &c.answer {
+        Answer::Text(text) => non_empty(r, &format!("{at}.answer"), text),
+        Answer::Details(_) if c.answer.is_blank() => {
+            r.error(format!("{at}.answer"), "must have an artist or a title")
+        }
+        Answer::Details(_) => {}
+    }
+    let start_ms = check_timecode(r, &format!("{at}.start"), c.start, fps);
+
+    // per-clip overrides (optional)
+    let guess_ms = match c.guess_duration {
+        Some(g) => check_duration(r, &format!("{at}.guess_duration"), g, fps),
+        None => default_guess_ms,
+    };
+    if let Some(d) = c.reveal_duration {
+        check_duration(r, &format!("{at}.reveal_duration"), d, fps);
+    }
+    let hint_ms = match c.hint_duration {
+        Some(h) => check_duration(r, &format!("{at}.hint_duration"), h, fps),
+        None => default_hint_ms,
+    };
+    if let Some(hint) = c.hint.as_deref() {
+        non_empty(r, &format!("{at}.hint"), hint);
+        if hint_ms.is_none() && c.hint_duration.is_none() {
+            r.error(
+                format!("{at}.hint"),
+                "needs a hint_duration (in the clip or in timings)",
+            );
+        }
     }
-    if reveal_ms == 0 {
-        bail!("timings.reveal_duration must be > 0");
+    if let Some(end) = c.end {
+        if c.reveal_duration.is_some() {
+            r.error(
+                format!("{at}.end"),
+                format!("cannot be set together with {at}.reveal_duration"),
+            );
+        }
+        if let (Some(end_ms), Some(start_ms), Some(guess_ms)) = (
+            check_timecode(r, &format!("{at}.end"), end, fps),
+            start_ms,
+            guess_ms,
+        ) && end_ms <= start_ms + guess_ms + hint_ms.unwrap_or(0)
+        {
+            let phases = if hint_ms.is_some() {
+                "guess_duration + hint_duration"
+            } else {
+                "guess_duration"
+            };
+            r.error(
+                format!("{at}.end"),
+                format!("must be after {at}.start + {phases}"),
+            );
+        }
     }
+}
+
+/// Warn about clips that repeat an earlier answer or excerpt.
+fn check_duplicates(r: &mut ValidationReport, p: &Project) {
+    let mut answers: HashMap<String, String> = HashMap::new();
+    let mut excerpts: HashMap<(String, u64), String> = HashMap::new();
 
-    // clips: at least one
-    if p.clips.is_empty() {
-        bail!("clips must not be empty");
+    for (at, c) in p.clip_paths() {
+        let answer = c.answer.summary().to_lowercase();
+        if !answer.is_empty() {
+            match answers.get(&answer) {
+                Some(first) => r.warning(
+                    format!("{at}.answer"),
+                    format!("is the same as {first}.answer"),
+                ),
+                None => {
+                    answers.insert(answer, at.clone());
+                }
+            }
+        }
+
+        let excerpt = (c.video.trim().to_string(), c.start.as_ms(p.fps()));
+        match excerpts.get(&excerpt) {
+            Some(first) => r.warning(at, format!("is the same excerpt as {first}")),
+            None => {
+                excerpts.insert(excerpt, at);
+            }
+        }
     }
+}
 
-    for (i, c) in p.clips.iter().enumerate() {
-        if c.video.trim().is_empty() {
-            bail!("clips[{i}].video must not be empty");
+/// In multiple-choice mode, every clip needs enough wrong options.
+fn check_quiz(r: &mut ValidationReport, p: &Project) {
+    if p.quiz.is_none() {
+        return;
+    }
+    for (at, c) in p.clip_paths() {
+        let available = distractor_candidates(p, c).len();
+        if available < CHOICES - 1 {
+            r.error(
+                format!("{at}.distractors"),
+                format!(
+                    "needs {} distractors in quiz mode, only {available} available \
+(add distractors or more clips)",
+                    CHOICES - 1
+                ),
+            );
         }
-        if c.answer.trim().is_empty() {
-            bail!("clips[{i}].answer must not be empty");
+    }
+}
+
+/// Check the values of a theme (inline, or read from a theme file).
+fn check_theme(r: &mut ValidationReport, at: &str, t: &Theme) {
+    if let Some(base) = t.extends.as_deref()
+        && builtin_theme(base.trim()).is_none()
+    {
+        r.error(
+            format!("{at}.extends"),
+            format!("must be a built-in theme ({})", BUILTIN_THEMES.join(", ")),
+        );
+    }
+    for (field, path) in [("font", &t.font), ("guess_image", &t.guess_image)] {
+        if let Some(path) = path {
+            non_empty(r, &format!("{at}.{field}"), path);
         }
-        parse_timecode_ms(c.start.trim())
-            .with_context(|| format!("clips[{i}].start is invalid"))?;
+    }
+    for (field, color) in [
+        ("background", &t.background),
+        ("text_color", &t.text_color),
+        ("border_color", &t.border_color),
+        ("highlight", &t.highlight),
+    ] {
+        check_color(r, &format!("{at}.{field}"), color.as_deref());
     }
 
-    // Optional output validation (light, V1):
-    if let Some(fps) = p.output.fps {
-        if fps == 0 {
-            bail!("output.fps must be > 0");
+    for (field, style) in [
+        ("title", &t.title),
+        ("countdown", &t.countdown),
+        ("options", &t.options),
+        ("hint", &t.hint),
+        ("answer", &t.answer),
+    ] {
+        let Some(style) = style else { continue };
+        let at = format!("{at}.{field}");
+        if style.size == Some(0) {
+            r.error(format!("{at}.size"), "must be > 0");
+        }
+        if style.y.is_some_and(|y| y > 100) {
+            r.error(format!("{at}.y"), "must be between 0 and 100");
+        } else if field == "options"
+            && let Some(y) = style.y.filter(|&y| y > MAX_OPTIONS_Y)
+        {
+            r.error(
+                format!("{at}.y"),
+                format!(
+                    "must be at most {MAX_OPTIONS_Y} for the {CHOICES} quiz options to fit on screen \
+({OPTION_SPACING}% apart), got {y}"
+                ),
+            );
+        }
+        for (field, color) in [
+            ("color", &style.color),
+            ("border_color", &style.border_color),
+            ("box_color", &style.box_color),
+        ] {
+            check_color(r, &format!("{at}.{field}"), color.as_deref());
+        }
+    }
+}
+
+/// Colours are passed to ffmpeg as is: a name or `#RRGGBB[AA]`, with an optional `@opacity`.
+fn check_color(r: &mut ValidationReport, at: &str, color: Option<&str>) {
+    let Some(color) = color.map(str::trim) else {
+        return;
+    };
+    let valid = !color.is_empty()
+        && color
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '#' | '@' | '.'));
+    if !valid {
+        r.error(
+            at,
+            "must be a colour name or #RRGGBB, with an optional @opacity (e.g. black@0.6)",
+        );
+    }
+}
+
+fn non_empty(r: &mut ValidationReport, at: &str, value: &str) {
+    if value.trim().is_empty() {
+        r.error(at, "must not be empty");
+    }
+}
+
+/// Check the frames of a timecode against the output fps; returns it in ms when valid.
+fn check_timecode(r: &mut ValidationReport, at: &str, value: Timecode, fps: u32) -> Option<u64> {
+    match value.check_fps(fps) {
+        Err(e) => {
+            r.error(at, format!("is invalid: {e:#}"));
+            None
         }
+        Ok(()) => Some(value.as_ms(fps)),
     }
+}
 
-    if let Some(res) = p.output.resolution.as_deref() {
-        let res = res.trim();
-        if !is_resolution(res) {
-            bail!("output.resolution must be formatted as WIDTHxHEIGHT (e.g. 1920x1080)");
+/// Check a duration that must be > 0; returns it in ms when valid.
+fn check_duration(r: &mut ValidationReport, at: &str, value: Timecode, fps: u32) -> Option<u64> {
+    match check_timecode(r, at, value, fps)? {
+        0 => {
+            r.error(at, "must be > 0");
+            None
         }
+        ms => Some(ms),
     }
+}
+
+/// Check that the theme file can be read and that its font exists.
+///
+/// Needs no probing, so it runs even without media checks (`--no-media`, or
+/// without ffprobe): a missing font would otherwise only fail inside ffmpeg.
+pub fn check_theme_files(p: &Project) -> ValidationReport {
+    let mut r = ValidationReport::default();
+    match project_theme(p) {
+        Err(e) => r.error("theme", format!("cannot be read: {e:#}")),
+        Ok(theme) => {
+            if matches!(p.theme, Some(ThemeRef::Name(_))) {
+                check_theme(&mut r, "theme", &theme);
+            }
+            if let Some(font) = theme.font.as_deref().map(str::trim)
+                && !font.is_empty()
+                && !Path::new(font).is_file()
+            {
+                r.error("theme.font", format!("does not exist: {font}"));
+            }
+        }
+    }
+    r
+}
 
-    Ok(())
+/// Check the project against the actual media files, using `ffprobe`.
+///
+/// Expects a project that already passed [`validate_project`].
+pub fn validate_media(p: &Project) -> Result<()> {
+    check_media(p).into_result()
+}
+
+/// Same checks as [`validate_media`], collecting every problem.
+pub fn check_media(p: &Project) -> ValidationReport {
+    check_media_with(p, |path| probe::probe(path))
+}
+
+/// Media checks with a custom probe function (useful for tests).
+///
+/// - every referenced file exists
+/// - clip sources have video + audio, and `start + guess + reveal` fits in the source
+/// - intro/outro music lasts at least `duration`
+/// - intro/outro/round backgrounds and the theme guess image are decodable images
+///
+/// The theme file and font are checked by [`check_theme_files`].
+pub fn check_media_with<F>(p: &Project, probe: F) -> ValidationReport
+where
+    F: Fn(&Path) -> Result<MediaInfo>,
+{
+    let mut r = ValidationReport::default();
+    let fps = p.fps();
+    let mut cache: HashMap<String, Option<MediaInfo>> = HashMap::new();
+
+    // Probe each file once; problems are reported on the first path using it.
+    let mut media = |r: &mut ValidationReport, at: &str, path: &str| -> Option<MediaInfo> {
+        let path = path.trim();
+        if let Some(info) = cache.get(path) {
+            return info.clone();
+        }
+        let info = if !Path::new(path).is_file() {
+            r.error(at, format!("does not exist: {path}"));
+            None
+        } else {
+            match probe(Path::new(path)) {
+                Ok(info) => Some(info),
+                Err(e) => {
+                    r.error(at, format!("cannot be read: {e:#}"));
+                    None
+                }
+            }
+        };
+        cache.insert(path.to_string(), info.clone());
+        info
+    };
+
+    if let Some(intro) = p.intro.as_ref() {
+        if let Some(info) = media(&mut r, "intro.background", &intro.background) {
+            check_image(&mut r, &info, "intro.background");
+        }
+        if let Some(info) = media(&mut r, "intro.music", &intro.music) {
+            check_music(&mut r, &info, "intro", intro.duration, fps);
+        }
+    }
+
+    for (ri, round) in p.rounds.iter().enumerate() {
+        if let Some(bg) = round.background.as_deref() {
+            let at = format!("rounds[{ri}].background");
+            if let Some(info) = media(&mut r, &at, bg) {
+                check_image(&mut r, &info, &at);
+            }
+        }
+    }
+
+    for (at, c) in p.clip_paths() {
+        let Some(info) = media(&mut r, &format!("{at}.video"), &c.video) else {
+            continue;
+        };
+        if !info.has_video() {
+            r.error(format!("{at}.video"), "has no video stream");
+        }
+        if !info.has_audio() {
+            r.error(format!("{at}.video"), "has no audio stream");
+        }
+
+        let Ok(timings) = resolve_clip_timings(c, &p.timings, fps) else {
+            continue;
+        };
+        let end_ms = c.start.as_ms(fps) + timings.total_ms();
+        if let Some(source_ms) = info.duration_ms
+            && end_ms > source_ms
+        {
+            r.error(
+                at,
+                format!(
+                    "ends at {} but {} only lasts {}",
+                    format_timecode_ms(end_ms),
+                    c.video.trim(),
+                    format_timecode_ms(source_ms)
+                ),
+            );
+        }
+    }
+
+    if let Some(outro) = p.outro.as_ref() {
+        if let Some(info) = media(&mut r, "outro.background", &outro.background) {
+            check_image(&mut r, &info, "outro.background");
+        }
+        if let Some(info) = media(&mut r, "outro.music", &outro.music) {
+            check_music(&mut r, &info, "outro", outro.duration, fps);
+        }
+    }
+
+    // Guess image (an unreadable theme file is reported by check_theme_files)
+    if let Ok(theme) = project_theme(p)
+        && let Some(image) = theme.guess_image.as_deref()
+        && let Some(info) = media(&mut r, "theme.guess_image", image)
+    {
+        check_image(&mut r, &info, "theme.guess_image");
+    }
+
+    r
+}
+
+fn check_image(r: &mut ValidationReport, info: &MediaInfo, at: &str) {
+    match info.video.as_ref() {
+        Some(v) if v.width > 0 && v.height > 0 => {}
+        _ => r.error(at, "is not a decodable image"),
+    }
+}
+
+fn check_music(
+    r: &mut ValidationReport,
+    info: &MediaInfo,
+    section: &str,
+    duration: Timecode,
+    fps: u32,
+) {
+    if !info.has_audio() {
+        r.error(format!("{section}.music"), "has no audio stream");
+        return;
+    }
+    let duration_ms = duration.as_ms(fps);
+    if let Some(music_ms) = info.duration_ms
+        && music_ms < duration_ms
+    {
+        r.error(
+            format!("{section}.music"),
+            format!(
+                "lasts {} but {section}.duration is {}",
+                format_timecode_ms(music_ms),
+                format_timecode_ms(duration_ms)
+            ),
+        );
+    }
 }
 
 fn is_resolution(s: &str) -> bool {
@@ -97,113 +642,642 @@ fn is_resolution(s: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{Clip, Intro, Output, Timings};
-
-    fn base_project() -> Project {
-        Project {
-            intro: None,
-            output: Output {
-                path: "render/out.mp4".into(),
-                resolution: Some("1920x1080".into()),
-                fps: Some(30),
-            },
-            timings: Timings {
-                guess_duration: "00:00:10.000".into(),
-                reveal_duration: "00:00:05.000".into(),
-            },
-            clips: vec![Clip {
-                video: "videos/a.mp4".into(),
-                start: "00:00:01.000".into(),
-                answer: "Artist - Track".into(),
-            }],
-        }
-    }
+    use crate::fixtures::{clip, intro, outro, round, sample_project, tc};
+    use crate::model::{AnswerDetails, Clip, Intro, Quiz, TextStyle};
+    use crate::probe::{AudioStream, VideoStream};
+    use std::fs;
 
     #[test]
     fn valid_project_passes() {
-        let p = base_project();
+        let p = sample_project();
         validate_project(&p).unwrap();
     }
 
     #[test]
     fn rejects_empty_clips() {
-        let mut p = base_project();
+        let mut p = sample_project();
         p.clips.clear();
         assert!(validate_project(&p).is_err());
     }
 
     #[test]
     fn rejects_empty_answer() {
-        let mut p = base_project();
+        let mut p = sample_project();
         p.clips[0].answer = "   ".into();
         assert!(validate_project(&p).is_err());
+
+        // A structured answer needs at least an artist or a title
+        p.clips[0].answer = Answer::Details(AnswerDetails {
+            year: Some(1985),
+            ..Default::default()
+        });
+        let errors: Vec<String> = check_project(&p).errors().map(|i| i.to_string()).collect();
+        assert_eq!(errors, ["clips[0].answer must have an artist or a title"]);
     }
 
     #[test]
-    fn rejects_invalid_start_timecode() {
-        let mut p = base_project();
-        p.clips[0].start = "banana".into();
-        assert!(validate_project(&p).is_err());
+    fn rejects_frames_beyond_fps() {
+        let mut p = sample_project();
+        p.clips[0].start = tc("00:00:01:30");
+        let err = validate_project(&p).unwrap_err().to_string();
+        assert!(err.contains("clips[0].start is invalid"), "{err}");
+    }
+
+    #[test]
+    fn accepts_friendly_timecodes() {
+        let mut p = sample_project();
+        p.timings.guess_duration = tc("10s");
+        p.timings.reveal_duration = tc("0:05");
+        p.clips[0].start = tc("1m30.5s");
+        p.clips[0].end = Some(tc("00:01:50:24"));
+        assert!(validate_project(&p).is_ok());
+
+        // SMPTE frames are checked against output.fps
+        p.output.fps = Some(24);
+        let err = validate_project(&p).unwrap_err().to_string();
+        assert!(err.contains("clips[0].end"), "{err}");
     }
 
     #[test]
     fn rejects_zero_durations() {
-        let mut p = base_project();
-        p.timings.guess_duration = "00:00:00.000".into();
+        let mut p = sample_project();
+        p.timings.guess_duration = tc("00:00:00.000");
         assert!(validate_project(&p).is_err());
 
-        let mut p = base_project();
-        p.timings.reveal_duration = "00:00:00.000".into();
+        let mut p = sample_project();
+        p.timings.reveal_duration = tc("00:00:00.000");
         assert!(validate_project(&p).is_err());
     }
 
     #[test]
     fn rejects_bad_resolution() {
-        let mut p = base_project();
+        let mut p = sample_project();
         p.output.resolution = Some("1920-1080".into());
         assert!(validate_project(&p).is_err());
     }
 
     #[test]
     fn rejects_fps_zero() {
-        let mut p = base_project();
+        let mut p = sample_project();
         p.output.fps = Some(0);
         assert!(validate_project(&p).is_err());
     }
 
     #[test]
-    fn intro_valid_passes() {
-        let mut p = base_project();
-        p.intro = Some(Intro {
-            background: "assets/intro.png".into(),
-            title: "Blind Test".into(),
-            music: "assets/intro.mp3".into(),
-            duration: "00:00:03.000".into(),
+    fn clip_timing_overrides_pass() {
+        let mut p = sample_project();
+        p.clips[0].guess_duration = Some(tc("00:00:20.000"));
+        p.clips[0].reveal_duration = Some(tc("00:00:08.000"));
+        validate_project(&p).unwrap();
+
+        let mut p = sample_project();
+        p.clips[0].end = Some(tc("00:00:30.000"));
+        validate_project(&p).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_clip_overrides() {
+        let mut p = sample_project();
+        p.clips[0].guess_duration = Some(tc("00:00:00.000"));
+        assert!(validate_project(&p).is_err());
+
+        let mut p = sample_project();
+        p.clips[0].reveal_duration = Some(tc("00:00:00.000"));
+        assert!(validate_project(&p).is_err());
+    }
+
+    #[test]
+    fn rejects_end_before_guess_is_over() {
+        let mut p = sample_project();
+        // start 1s + guess 10s => end must be > 11s
+        p.clips[0].end = Some(tc("00:00:11.000"));
+        assert!(validate_project(&p).is_err());
+    }
+
+    #[test]
+    fn checks_hint_phase() {
+        let mut p = sample_project();
+        p.timings.hint_duration = Some(tc("3s"));
+        p.clips[0].hint = Some("1985 · Pop".into());
+        validate_project(&p).unwrap();
+
+        p.timings.hint_duration = None;
+        p.clips.push(Clip {
+            hint: Some(" ".into()),
+            hint_duration: Some(tc("0s")),
+            ..p.clips[0].clone()
         });
+
+        let errors: Vec<String> = check_project(&p).errors().map(|i| i.to_string()).collect();
+        assert_eq!(
+            errors,
+            [
+                "clips[0].hint needs a hint_duration (in the clip or in timings)",
+                "clips[1].hint_duration must be > 0",
+                "clips[1].hint must not be empty",
+            ]
+        );
+
+        // start 1s + guess 10s + hint 3s => end must be > 14s
+        let mut p = sample_project();
+        p.clips[0].hint_duration = Some(tc("3s"));
+        p.clips[0].end = Some(tc("14s"));
+        let errors: Vec<String> = check_project(&p).errors().map(|i| i.to_string()).collect();
+        assert_eq!(
+            errors,
+            ["clips[0].end must be after clips[0].start + guess_duration + hint_duration"]
+        );
+    }
+
+    #[test]
+    fn rejects_end_with_reveal_duration() {
+        let mut p = sample_project();
+        p.clips[0].end = Some(tc("00:00:30.000"));
+        p.clips[0].reveal_duration = Some(tc("00:00:05.000"));
+        assert!(validate_project(&p).is_err());
+    }
+
+    #[test]
+    fn rounds_only_project_passes() {
+        let mut p = sample_project();
+        let clips = std::mem::take(&mut p.clips);
+        p.rounds.push(round("Années 80", clips));
+        validate_project(&p).unwrap();
+    }
+
+    #[test]
+    fn rejects_empty_round() {
+        let mut p = sample_project();
+        p.rounds.push(round("Films", Vec::new()));
+        assert!(validate_project(&p).is_err());
+
+        let mut p = sample_project();
+        let clips = p.clips.clone();
+        p.rounds.push(round("  ", clips));
+        assert!(validate_project(&p).is_err());
+    }
+
+    #[test]
+    fn reports_round_clip_path() {
+        let mut p = sample_project();
+        let mut clips = p.clips.clone();
+        clips[0].answer = "".into();
+        p.rounds.push(round("Rap FR", clips));
+
+        let err = validate_project(&p).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "rounds[0].clips[0].answer must not be empty"
+        );
+    }
+
+    #[test]
+    fn intro_valid_passes() {
+        let mut p = sample_project();
+        p.intro = Some(intro("Blind Test", "00:00:03.000"));
         validate_project(&p).unwrap();
     }
 
     #[test]
     fn intro_rejects_zero_duration() {
-        let mut p = base_project();
-        p.intro = Some(Intro {
-            background: "assets/intro.png".into(),
-            title: "Blind Test".into(),
-            music: "assets/intro.mp3".into(),
-            duration: "00:00:00.000".into(),
-        });
+        let mut p = sample_project();
+        p.intro = Some(intro("Blind Test", "00:00:00.000"));
         assert!(validate_project(&p).is_err());
     }
 
     #[test]
     fn intro_rejects_empty_fields() {
-        let mut p = base_project();
+        let mut p = sample_project();
         p.intro = Some(Intro {
             background: "   ".into(),
-            title: "".into(),
             music: " ".into(),
-            duration: "00:00:03.000".into(),
+            ..intro("", "00:00:03.000")
         });
         assert!(validate_project(&p).is_err());
     }
+
+    #[test]
+    fn outro_valid_passes() {
+        let mut p = sample_project();
+        p.outro = Some(outro("Merci d'avoir joué", "00:00:05.000"));
+        validate_project(&p).unwrap();
+    }
+
+    #[test]
+    fn outro_rejects_invalid_fields() {
+        let mut p = sample_project();
+        p.outro = Some(outro(" ", "00:00:05.000"));
+        assert!(validate_project(&p).is_err());
+
+        let mut p = sample_project();
+        p.outro = Some(outro("Merci", "00:00:00.000"));
+        assert!(validate_project(&p).is_err());
+    }
+
+    // --- report ---
+
+    #[test]
+    fn collects_every_error() {
+        let mut p = sample_project();
+        p.output.path = " ".into();
+        p.timings.reveal_duration = tc("00:00:00.000");
+        p.clips[0].start = tc("00:00:01:12");
+        p.clips.push(clip("", "00:00:02.000", ""));
+        p.output.fps = Some(0);
+
+        let report = check_project(&p);
+        let paths: Vec<&str> = report.errors().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "output.path",
+                "timings.reveal_duration",
+                "clips[0].start",
+                "clips[1].video",
+                "clips[1].answer",
+                "output.fps",
+            ]
+        );
+
+        let err = report.into_result().unwrap_err().to_string();
+        assert_eq!(err.lines().count(), 6, "{err}");
+        assert!(err.starts_with("output.path must not be empty\n"), "{err}");
+    }
+
+    #[test]
+    fn warns_about_duplicates_without_failing() {
+        let mut p = sample_project();
+        let mut dup = p.clips[0].clone();
+        dup.answer = " artist - track ".into();
+        p.rounds.push(round("Bonus", vec![dup]));
+
+        let report = check_project(&p);
+        assert!(!report.has_errors());
+
+        let warnings: Vec<String> = report.warnings().map(|i| i.to_string()).collect();
+        assert_eq!(
+            warnings,
+            [
+                "rounds[0].clips[0].answer is the same as clips[0].answer",
+                "rounds[0].clips[0] is the same excerpt as clips[0]",
+            ]
+        );
+        validate_project(&p).unwrap();
+    }
+
+    #[test]
+    fn quiz_needs_enough_distractors() {
+        let mut p = sample_project();
+        p.quiz = Some(Quiz::default());
+        p.rounds.push(round(
+            "Bonus",
+            vec![Clip {
+                answer: "Other".into(),
+                distractors: vec!["A".into(), "B".into(), "other".into()],
+                ..p.clips[0].clone()
+            }],
+        ));
+
+        // The round clip gets "A", "B" and the other answer ("other" is its own answer)
+        let errors: Vec<String> = check_project(&p).errors().map(|i| i.to_string()).collect();
+        assert_eq!(
+            errors,
+            [
+                "clips[0].distractors needs 3 distractors in quiz mode, only 1 available \
+(add distractors or more clips)",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_theme_values() {
+        let mut p = sample_project();
+        for name in ["neon", " minimal ", "themes/soiree.yaml"] {
+            p.theme = Some(ThemeRef::Name(name.into()));
+            validate_project(&p).unwrap();
+        }
+
+        p.theme = Some(ThemeRef::Name("neo".into()));
+        let errors: Vec<String> = check_project(&p).errors().map(|i| i.to_string()).collect();
+        assert_eq!(
+            errors,
+            [
+                "theme is neither a built-in theme (classic, neon, minimal) nor a .json, .yaml or .toml theme file"
+            ]
+        );
+
+        p.theme = Some(ThemeRef::Inline(Box::new(Theme {
+            extends: Some("retro".into()),
+            font: Some(" ".into()),
+            background: Some("#0d0221".into()),
+            highlight: Some("green; rm".into()),
+            options: Some(TextStyle {
+                y: Some(80),
+                ..Default::default()
+            }),
+            answer: Some(TextStyle {
+                size: Some(0),
+                y: Some(120),
+                box_color: Some("black@0.6".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })));
+        let errors: Vec<String> = check_project(&p).errors().map(|i| i.to_string()).collect();
+        assert_eq!(
+            errors,
+            [
+                "theme.extends must be a built-in theme (classic, neon, minimal)",
+                "theme.font must not be empty",
+                "theme.highlight must be a colour name or #RRGGBB, with an optional @opacity (e.g. black@0.6)",
+                "theme.options.y must be at most 58 for the 4 quiz options to fit on screen \
+(12% apart), got 80",
+                "theme.answer.size must be > 0",
+                "theme.answer.y must be between 0 and 100",
+            ]
+        );
+    }
+
+    #[test]
+    fn serializes_report_for_tools() {
+        let mut p = sample_project();
+        p.clips[0].answer = "".into();
+
+        let json = serde_json::to_value(check_project(&p)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "issues": [
+                    { "severity": "error", "path": "clips[0].answer", "message": "must not be empty" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn media_collects_every_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut p = media_project(dir.path());
+        p.clips.push(clip(
+            &dir.path().join("missing.mp4").to_string_lossy(),
+            "00:00:00.000",
+            "B",
+        ));
+
+        let report = check_media_with(&p, fake_probe(10_000, false, 1_000));
+        let paths: Vec<&str> = report.errors().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "intro.music",
+                "clips[0].video",
+                "clips[0]",
+                "clips[1].video"
+            ]
+        );
+    }
+
+    // --- media validation ---
+
+    fn video_info(duration_ms: u64, audio: bool) -> MediaInfo {
+        MediaInfo {
+            duration_ms: Some(duration_ms),
+            video: Some(VideoStream {
+                codec: "h264".into(),
+                width: 1920,
+                height: 1080,
+                fps: Some(30.0),
+                rotation: 0,
+            }),
+            audio: audio.then(|| AudioStream {
+                codec: "aac".into(),
+                sample_rate: Some(48_000),
+                channels: Some(2),
+            }),
+            tags: Default::default(),
+        }
+    }
+
+    fn image_info() -> MediaInfo {
+        MediaInfo {
+            duration_ms: None,
+            audio: None,
+            ..video_info(0, false)
+        }
+    }
+
+    fn music_info(duration_ms: u64) -> MediaInfo {
+        MediaInfo {
+            video: None,
+            ..video_info(duration_ms, true)
+        }
+    }
+
+    /// Project whose files exist in `dir`; probe results depend on the extension.
+    fn media_project(dir: &Path) -> Project {
+        for f in ["a.mp4", "intro.png", "intro.mp3"] {
+            fs::write(dir.join(f), b"").unwrap();
+        }
+        let path = |f: &str| dir.join(f).to_string_lossy().to_string();
+
+        let mut p = sample_project();
+        p.clips[0].video = path("a.mp4");
+        p.intro = Some(Intro {
+            background: path("intro.png"),
+            music: path("intro.mp3"),
+            ..intro("Blind Test", "00:00:05.000")
+        });
+        p
+    }
+
+    fn fake_probe(
+        clip_ms: u64,
+        clip_audio: bool,
+        music_ms: u64,
+    ) -> impl Fn(&Path) -> Result<MediaInfo> {
+        move |path: &Path| {
+            Ok(match path.extension().and_then(|e| e.to_str()) {
+                Some("png") => image_info(),
+                Some("mp3") => music_info(music_ms),
+                _ => video_info(clip_ms, clip_audio),
+            })
+        }
+    }
+
+    #[test]
+    fn media_valid_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = media_project(dir.path());
+        // clip window: 1s + 10s + 5s = 16s
+        check_media_with(&p, fake_probe(16_000, true, 5_000))
+            .into_result()
+            .unwrap();
+    }
+
+    #[test]
+    fn media_rejects_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut p = media_project(dir.path());
+        p.clips[0].video = dir.path().join("nope.mp4").to_string_lossy().to_string();
+
+        let err = check_media_with(&p, fake_probe(60_000, true, 60_000))
+            .into_result()
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("clips[0].video does not exist: "),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn media_rejects_clip_past_source_end() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = media_project(dir.path());
+
+        let err = check_media_with(&p, fake_probe(15_999, true, 60_000))
+            .into_result()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "clips[0] ends at 00:00:16.000 but {} only lasts 00:00:15.999",
+                p.clips[0].video
+            )
+        );
+    }
+
+    #[test]
+    fn media_rejects_clip_without_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = media_project(dir.path());
+
+        let err = check_media_with(&p, fake_probe(60_000, false, 60_000))
+            .into_result()
+            .unwrap_err();
+        assert!(err.to_string().contains("no audio stream"), "{err}");
+    }
+
+    #[test]
+    fn media_rejects_short_intro_music() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = media_project(dir.path());
+
+        let err = check_media_with(&p, fake_probe(60_000, true, 4_000))
+            .into_result()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "intro.music lasts 00:00:04.000 but intro.duration is 00:00:05.000"
+        );
+    }
+
+    #[test]
+    fn media_rejects_undecodable_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = media_project(dir.path());
+
+        let probe = |path: &Path| -> Result<MediaInfo> {
+            if path.extension().and_then(|e| e.to_str()) == Some("png") {
+                bail!("Invalid data found when processing input");
+            }
+            fake_probe(60_000, true, 60_000)(path)
+        };
+        let err = check_media_with(&p, probe).into_result().unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("intro.background cannot be read: Invalid data found"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn media_checks_theme_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut p = media_project(dir.path());
+        let path = |f: &str| dir.path().join(f).to_string_lossy().to_string();
+        fs::write(dir.path().join("guess.png"), b"").unwrap();
+
+        p.theme = Some(ThemeRef::Inline(Box::new(Theme {
+            guess_image: Some(path("guess.png")),
+            ..Default::default()
+        })));
+        check_media_with(&p, fake_probe(16_000, true, 5_000))
+            .into_result()
+            .unwrap();
+
+        p.theme = Some(ThemeRef::Inline(Box::new(Theme {
+            font: Some(path("missing.ttf")),
+            guess_image: Some(path("missing.png")),
+            ..Default::default()
+        })));
+        let report = check_media_with(&p, fake_probe(16_000, true, 5_000));
+        let errors: Vec<String> = report.errors().map(|i| i.to_string()).collect();
+        assert_eq!(
+            errors,
+            [format!(
+                "theme.guess_image does not exist: {}",
+                path("missing.png")
+            )]
+        );
+    }
+
+    #[test]
+    fn theme_files_are_checked_without_probing() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut p = sample_project();
+        let path = |f: &str| dir.path().join(f).to_string_lossy().to_string();
+        fs::write(dir.path().join("title.ttf"), b"").unwrap();
+
+        p.theme = Some(ThemeRef::Inline(Box::new(Theme {
+            font: Some(path("title.ttf")),
+            ..Default::default()
+        })));
+        check_theme_files(&p).into_result().unwrap();
+
+        p.theme = Some(ThemeRef::Inline(Box::new(Theme {
+            font: Some(path("missing.ttf")),
+            ..Default::default()
+        })));
+        let errors: Vec<String> = check_theme_files(&p)
+            .errors()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            [format!(
+                "theme.font does not exist: {}",
+                path("missing.ttf")
+            )]
+        );
+
+        // Theme files are read, then checked like inline themes
+        fs::write(
+            dir.path().join("theme.yaml"),
+            "font: title.ttf\nhighlight: ''\n",
+        )
+        .unwrap();
+        p.theme = Some(ThemeRef::Name(path("theme.yaml")));
+        let errors: Vec<String> = check_theme_files(&p)
+            .errors()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                "theme.highlight must be a colour name or #RRGGBB, with an optional @opacity (e.g. black@0.6)"
+            ]
+        );
+
+        p.theme = Some(ThemeRef::Name(path("nope.toml")));
+        let report = check_theme_files(&p);
+        let errors: Vec<String> = report.errors().map(|i| i.to_string()).collect();
+        assert_eq!(
+            errors,
+            [format!(
+                "theme cannot be read: failed to read theme file: {}: No such file or directory (os error 2)",
+                path("nope.toml")
+            )]
+        );
+    }
 }
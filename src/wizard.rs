@@ -1,5 +1,9 @@
-use crate::model::{Clip, Intro, Output, Project, Timings};
-use crate::timecode::parse_timecode_ms;
+use crate::format::ProjectFormat;
+use crate::model::{
+    CURRENT_VERSION, Clip, DEFAULT_FPS, Intro, Output, Outro, Project, Round, Timings,
+};
+use crate::probe::{self, MediaInfo};
+use crate::timecode::{Timecode, format_timecode_ms};
 use anyhow::{Context, Result, bail};
 use inquire::{Confirm, Text};
 use rand::seq::SliceRandom;
@@ -9,10 +13,31 @@ use std::path::{Path, PathBuf};
 
 /// Wizard interactif (assistant guidé)
 pub fn run_new_wizard() -> Result<(Project, String)> {
-    let json_path = Text::new("Chemin du fichier JSON à générer ?")
+    let json_path = Text::new("Chemin du fichier projet à générer ? (.json, .yaml ou .toml)")
         .with_default("montage.json")
         .prompt()?;
 
+    // --- OUTPUT ---
+    let output_path = Text::new("Fichier vidéo de sortie ?")
+        .with_default("render/blindtest.mp4")
+        .prompt()?;
+
+    let resolution = Text::new("Résolution (optionnel, ex: 1280x720) (laisser vide pour défaut)")
+        .with_default("")
+        .prompt()?;
+    let resolution = (!resolution.trim().is_empty()).then(|| resolution.trim().to_string());
+
+    let fps = Text::new("FPS (optionnel, ex: 30) (laisser vide pour défaut)")
+        .with_default("")
+        .prompt()?;
+    let fps = if fps.trim().is_empty() {
+        None
+    } else {
+        Some(fps.trim().parse::<u32>().context("FPS invalide")?)
+    };
+    // Frame rate for SMPTE timecodes (HH:MM:SS:FF), asked before any duration
+    let tc_fps = fps.unwrap_or(DEFAULT_FPS);
+
     // --- INTRO (optionnelle) ---
     let add_intro =
         Confirm::new("Ajouter une introduction (image + titre + musique) avant le blindtest ?")
@@ -32,7 +57,11 @@ pub fn run_new_wizard() -> Result<(Project, String)> {
             .with_default("assets/intro.mp3")
             .prompt()?;
 
-        let duration = prompt_timecode("Durée de l'intro (HH:MM:SS.mmm) ?", "00:00:05.000")?;
+        let duration = prompt_timecode(
+            "Durée de l'intro (ex: 5s, 00:00:05.000) ?",
+            "00:00:05.000",
+            tc_fps,
+        )?;
 
         Some(Intro {
             background: background.trim().to_string(),
@@ -44,28 +73,51 @@ pub fn run_new_wizard() -> Result<(Project, String)> {
         None
     };
 
-    // --- OUTPUT ---
-    let output_path = Text::new("Fichier vidéo de sortie ?")
-        .with_default("render/blindtest.mp4")
+    // --- OUTRO (optionnel) ---
+    let add_outro = Confirm::new("Ajouter un écran de fin (image + texte + musique) ?")
+        .with_default(false)
         .prompt()?;
 
-    let resolution = Text::new("Résolution (optionnel, ex: 1280x720) (laisser vide pour défaut)")
-        .with_default("")
-        .prompt()?;
-    let resolution = (!resolution.trim().is_empty()).then(|| resolution.trim().to_string());
+    let outro: Option<Outro> = if add_outro {
+        let background = Text::new("Chemin de l'image de fond (ex: assets/outro.png) ?")
+            .with_default("assets/outro.png")
+            .prompt()?;
 
-    let fps = Text::new("FPS (optionnel, ex: 30) (laisser vide pour défaut)")
-        .with_default("")
-        .prompt()?;
-    let fps = if fps.trim().is_empty() {
-        None
+        let text = Text::new("Texte affiché sur l'écran de fin ?")
+            .with_default("Merci d'avoir joué")
+            .prompt()?;
+
+        let music = Text::new("Chemin de la musique de fin (ex: assets/outro.mp3) ?")
+            .with_default("assets/outro.mp3")
+            .prompt()?;
+
+        let duration = prompt_timecode(
+            "Durée de l'écran de fin (ex: 5s, 00:00:05.000) ?",
+            "00:00:05.000",
+            tc_fps,
+        )?;
+
+        Some(Outro {
+            background: background.trim().to_string(),
+            text: text.trim().to_string(),
+            music: music.trim().to_string(),
+            duration,
+        })
     } else {
-        Some(fps.trim().parse::<u32>().context("FPS invalide")?)
+        None
     };
 
     // --- TIMINGS ---
-    let guess_duration = prompt_timecode("Durée devinette (HH:MM:SS.mmm)", "00:00:10.000")?;
-    let reveal_duration = prompt_timecode("Durée révélation (HH:MM:SS.mmm)", "00:00:05.000")?;
+    let guess_duration = prompt_timecode(
+        "Durée devinette (ex: 10s, 00:00:10.000)",
+        "00:00:10.000",
+        tc_fps,
+    )?;
+    let reveal_duration = prompt_timecode(
+        "Durée révélation (ex: 5s, 00:00:05.000)",
+        "00:00:05.000",
+        tc_fps,
+    )?;
 
     // --- CLIPS ---
     let mut clips: Vec<Clip> = Vec::new();
@@ -81,14 +133,29 @@ pub fn run_new_wizard() -> Result<(Project, String)> {
             .with_default("videos/clip.mp4")
             .prompt()?;
 
-        let start = prompt_timecode("Timecode de départ (HH:MM:SS.mmm)", "00:00:00.000")?;
+        // Infos du fichier source (si ffprobe est disponible)
+        let media = probe::probe(video.trim()).ok();
+        if let Some(ms) = media.as_ref().and_then(|m| m.duration_ms) {
+            println!("ℹ️  Durée de la vidéo : {}", format_timecode_ms(ms));
+        }
 
-        // UX: propose par défaut le nom de fichier comme réponse (super pratique)
-        let default_answer = Path::new(video.trim())
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("Artiste - Titre")
-            .to_string();
+        let start = prompt_timecode(
+            "Timecode de départ (ex: 1:30, 00:01:30.000)",
+            "00:00:00.000",
+            tc_fps,
+        )?;
+
+        // UX: propose par défaut les tags artiste/titre, sinon le nom de fichier comme réponse
+        let default_answer = media
+            .as_ref()
+            .and_then(answer_from_tags)
+            .unwrap_or_else(|| {
+                Path::new(video.trim())
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("Artiste - Titre")
+                    .to_string()
+            });
 
         let answer = Text::new("Réponse à afficher (titre / artiste)")
             .with_default(&default_answer)
@@ -97,11 +164,13 @@ pub fn run_new_wizard() -> Result<(Project, String)> {
         clips.push(Clip {
             video: video.trim().to_string(),
             start,
-            answer: answer.trim().to_string(),
+            answer: answer.trim().into(),
+            ..Default::default()
         });
     }
 
     let project = Project {
+        version: CURRENT_VERSION,
         intro,
         output: Output {
             path: output_path.trim().to_string(),
@@ -111,8 +180,14 @@ pub fn run_new_wizard() -> Result<(Project, String)> {
         timings: Timings {
             guess_duration,
             reveal_duration,
+            hint_duration: None,
         },
         clips,
+        rounds: Vec::new(),
+        outro,
+        schema: None,
+        quiz: None,
+        theme: None,
     };
 
     Ok((project, json_path))
@@ -149,16 +224,27 @@ pub fn run_quick(folder: PathBuf, shuffle: bool) -> Result<(Project, String)> {
         .iter()
         .map(|p| Clip {
             video: p.to_string_lossy().to_string(),
-            start: "00:00:00.000".into(),
-            answer: p
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("Unknown")
-                .to_string(),
+            start: Timecode::ZERO,
+            answer: answer_from_file_name(p).into(),
+            ..Default::default()
         })
         .collect();
 
-    let project = Project {
+    Ok((default_project(clips, Vec::new()), "montage.json".into()))
+}
+
+/// Réponse par défaut d'un clip : le nom du fichier sans extension
+pub fn answer_from_file_name(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Unknown")
+        .to_string()
+}
+
+/// Project with the quick mode defaults (720p, 10 s + 5 s) around the given clips
+pub fn default_project(clips: Vec<Clip>, rounds: Vec<Round>) -> Project {
+    Project {
+        version: CURRENT_VERSION,
         intro: None,
         output: Output {
             path: "render/blindtest.mp4".into(),
@@ -166,39 +252,57 @@ pub fn run_quick(folder: PathBuf, shuffle: bool) -> Result<(Project, String)> {
             fps: Some(30),
         },
         timings: Timings {
-            guess_duration: "00:00:10.000".into(),
-            reveal_duration: "00:00:05.000".into(),
+            guess_duration: Timecode::from_secs(10),
+            reveal_duration: Timecode::from_secs(5),
+            hint_duration: None,
         },
         clips,
-    };
-
-    Ok((project, "montage.json".into()))
+        rounds,
+        outro: None,
+        schema: None,
+        quiz: None,
+        theme: None,
+    }
 }
 
-/// Écriture du JSON pretty
+/// Écriture du projet, en JSON pretty ou en YAML / TOML selon l'extension du fichier
 pub fn write_project_json<P: AsRef<Path>>(path: P, project: &Project) -> Result<()> {
-    let json = serde_json::to_string_pretty(project).context("impossible de sérialiser le JSON")?;
     let path = path.as_ref();
-
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent).ok();
-        }
+    let format = ProjectFormat::from_path(path);
+    let text = format
+        .to_string_pretty(project)
+        .with_context(|| format!("impossible de sérialiser le {}", format.name()))?;
+
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).ok();
     }
 
-    fs::write(path, json).with_context(|| format!("Impossible d'écrire {}", path.display()))?;
+    fs::write(path, text).with_context(|| format!("Impossible d'écrire {}", path.display()))?;
     Ok(())
 }
 
-fn prompt_timecode(question: &str, default: &str) -> Result<String> {
+/// "Artiste - Titre" depuis les tags du fichier (ou juste le titre).
+fn answer_from_tags(media: &MediaInfo) -> Option<String> {
+    let title = media.tag("title")?;
+    Some(match media.tag("artist") {
+        Some(artist) => format!("{} - {}", artist.trim(), title.trim()),
+        None => title.trim().to_string(),
+    })
+}
+
+/// Ask for a timecode in any accepted form, normalised to milliseconds (`HH:MM:SS.mmm` in JSON).
+fn prompt_timecode(question: &str, default: &str, fps: u32) -> Result<Timecode> {
     loop {
         let tc = Text::new(question).with_default(default).prompt()?;
-        let tc = tc.trim().to_string();
 
-        if parse_timecode_ms(&tc).is_ok() {
-            return Ok(tc);
+        let parsed = tc
+            .parse::<Timecode>()
+            .and_then(|tc| tc.check_fps(fps).map(|()| tc.normalized(fps)));
+        match parsed {
+            Ok(tc) => return Ok(tc),
+            Err(e) => eprintln!("❌ {e:#}"),
         }
-
-        eprintln!("❌ Format invalide (ex: 00:00:10.000)");
     }
 }